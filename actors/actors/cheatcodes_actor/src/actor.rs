@@ -6,21 +6,68 @@ use frc42_dispatch::match_method;
 use fvm_ipld_encoding::{de::DeserializeOwned, RawBytes};
 use fvm_sdk::NO_DATA_BLOCK_ID;
 use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::event::ActorEvent;
+use fvm_shared::MethodNum;
 
-/// Deserialize message parameters into given struct.
-pub fn deserialize_params<D: DeserializeOwned>(params: u32) -> D {
+/// Get the raw message parameters.
+fn raw_params(params: u32) -> RawBytes {
     let params = fvm_sdk::message::params_raw(params)
         .expect("Could not get message parameters")
         .expect("Expected message parameters but got none");
 
-    let params = RawBytes::new(params.data);
+    RawBytes::new(params.data)
+}
+
+/// Deserialize message parameters into given struct.
+pub fn deserialize_params<D: DeserializeOwned>(params: u32) -> D {
+    let params = raw_params(params);
 
     params
         .deserialize()
         .expect("Should be able to deserialize message params into arguments of called method")
 }
 
+/// Deserialize message parameters into given struct from a CBOR array, or from its first field
+/// alone converted with `from_single`. The two shapes are told apart by the CBOR major type of the
+/// parameters.
+pub fn deserialize_params_or<D: DeserializeOwned, S: DeserializeOwned>(
+    params: u32,
+    from_single: impl FnOnce(S) -> D,
+) -> D {
+    let params = raw_params(params);
+
+    // CBOR arrays have the major type 4, held by the 3 high bits of their first byte.
+    let params = if params.bytes().first().is_some_and(|byte| byte >> 5 == 4) {
+        params.deserialize()
+    } else {
+        params.deserialize().map(from_single)
+    };
+    params.expect("Should be able to deserialize message params into arguments of called method")
+}
+
+/// Deserialize prank parameters, either a caller address or a caller address and an optional
+/// origin address.
+pub fn deserialize_prank_params(params: u32) -> (Address, Option<Address>) {
+    deserialize_params_or(params, |new_caller| (new_caller, None))
+}
+
+/// Deserialize new account parameters, either a label or a label, an optional balance and a key
+/// type.
+pub fn deserialize_new_account_params(params: u32) -> (String, Option<TokenAmount>, String) {
+    deserialize_params_or(params, |label| (label, None, String::from("Secp256k1")))
+}
+
+/// Deserialize randomness parameters, either a fixed randomness or a table of optional domain
+/// separation tag, optional epoch and randomness entries.
+pub fn deserialize_randomness_params(
+    params: u32,
+) -> Vec<(Option<i64>, Option<ChainEpoch>, RawBytes)> {
+    deserialize_params_or(params, |randomness| vec![(None, None, randomness)])
+}
+
 #[no_mangle]
 fn invoke(input: u32) -> u32 {
     let method_num = fvm_sdk::message::method_number();
@@ -66,9 +113,22 @@ fn invoke(input: u32) -> u32 {
             },
             "Prank" => {
                 // Ensure that the message params can be deserialized.
-                let new_caller: Address = deserialize_params(input);
+                let (new_caller, new_origin) = deserialize_prank_params(input);
+
+                Prank(new_caller, new_origin);
+
+                NO_DATA_BLOCK_ID
+            },
+            "StartPrank" => {
+                // Ensure that the message params can be deserialized.
+                let (new_caller, new_origin) = deserialize_prank_params(input);
 
-                Prank(new_caller);
+                StartPrank(new_caller, new_origin);
+
+                NO_DATA_BLOCK_ID
+            },
+            "StopPrank" => {
+                StopPrank();
 
                 NO_DATA_BLOCK_ID
             },
@@ -90,14 +150,226 @@ fn invoke(input: u32) -> u32 {
             }
             "Alter" => {
                 // Ensure that the message params can be deserialized.
-                let (address, cid_str): (Address, String) = deserialize_params(input);
-
-                let cid = Cid::try_from(cid_str).expect("Invalid Cid passed to Alter cheatcode");
+                let (address, cid): (Address, String) = deserialize_params(input);
 
                 Alter(address, cid);
 
                 NO_DATA_BLOCK_ID
             },
+            "Deal" => {
+                // Ensure that the message params can be deserialized.
+                let (address, balance): (Address, TokenAmount) = deserialize_params(input);
+
+                Deal(address, balance);
+
+                NO_DATA_BLOCK_ID
+            },
+            "ExpectRevert" => {
+                // Ensure that the message params can be deserialized.
+                let (exit_code, message): (ExitCode, Option<String>) = deserialize_params(input);
+
+                ExpectRevert(exit_code, message);
+
+                NO_DATA_BLOCK_ID
+            },
+            "ExpectEmit" => {
+                // Ensure that the message params can be deserialized.
+                let event: ActorEvent = deserialize_params(input);
+
+                ExpectEmit(event);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Snapshot" => {
+                Snapshot();
+
+                // The snapshot ID is returned by the call manager.
+                NO_DATA_BLOCK_ID
+            },
+            "RevertTo" => {
+                // Ensure that the message params can be deserialized.
+                let snapshot_id: u64 = deserialize_params(input);
+
+                RevertTo(snapshot_id);
+
+                NO_DATA_BLOCK_ID
+            },
+            "MockCall" => {
+                // Ensure that the message params can be deserialized.
+                let (to, method, params, return_bytes, exit_code): (
+                    Address,
+                    MethodNum,
+                    Option<RawBytes>,
+                    RawBytes,
+                    ExitCode,
+                ) = deserialize_params(input);
+
+                MockCall(to, method, params, return_bytes, exit_code);
+
+                NO_DATA_BLOCK_ID
+            },
+            "ClearMockedCalls" => {
+                ClearMockedCalls();
+
+                NO_DATA_BLOCK_ID
+            },
+            "ExpectCall" => {
+                // Ensure that the message params can be deserialized.
+                let (to, method, params, value, count): (
+                    Address,
+                    MethodNum,
+                    Option<RawBytes>,
+                    Option<TokenAmount>,
+                    u64,
+                ) = deserialize_params(input);
+
+                ExpectCall(to, method, params, value, count);
+
+                NO_DATA_BLOCK_ID
+            },
+            "SetChainRandomness" => {
+                // Ensure that the message params can be deserialized.
+                let entries = deserialize_randomness_params(input);
+
+                SetChainRandomness(entries);
+
+                NO_DATA_BLOCK_ID
+            },
+            "SetBeaconRandomness" => {
+                // Ensure that the message params can be deserialized.
+                let entries = deserialize_randomness_params(input);
+
+                SetBeaconRandomness(entries);
+
+                NO_DATA_BLOCK_ID
+            },
+            "SetTipsetCid" => {
+                // Ensure that the message params can be deserialized.
+                let (epoch, cid_str): (ChainEpoch, String) = deserialize_params(input);
+
+                let cid = Cid::try_from(cid_str).expect("Invalid Cid passed to SetTipsetCid cheatcode");
+
+                SetTipsetCid(epoch, cid);
+
+                NO_DATA_BLOCK_ID
+            },
+            "MockConsensusFault" => {
+                // Ensure that the message params can be deserialized.
+                let (h1, h2, extra, fault): (
+                    RawBytes,
+                    RawBytes,
+                    RawBytes,
+                    Option<(Address, ChainEpoch, u8)>,
+                ) = deserialize_params(input);
+
+                MockConsensusFault(h1, h2, extra, fault);
+
+                NO_DATA_BLOCK_ID
+            },
+            "MockProofs" => {
+                // Ensure that the message params can be deserialized.
+                let mock_proofs: bool = deserialize_params(input);
+
+                MockProofs(mock_proofs);
+
+                NO_DATA_BLOCK_ID
+            },
+            "ExpectVerifyProof" => {
+                // Ensure that the message params can be deserialized.
+                let (kind, verdict): (String, bool) = deserialize_params(input);
+
+                ExpectVerifyProof(kind, verdict);
+
+                NO_DATA_BLOCK_ID
+            },
+            "NewAccount" => {
+                // Ensure that the message params can be deserialized.
+                let (label, balance, key_type) = deserialize_new_account_params(input);
+
+                NewAccount(label, balance, key_type);
+
+                NO_DATA_BLOCK_ID
+            },
+            "AddrFromKey" => {
+                // Ensure that the message params can be deserialized.
+                let key: RawBytes = deserialize_params(input);
+
+                AddrFromKey(key);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Sign" => {
+                // Ensure that the message params can be deserialized.
+                let (account, digest): (Address, RawBytes) = deserialize_params(input);
+
+                Sign(account, digest);
+
+                NO_DATA_BLOCK_ID
+            },
+            "MockSignatures" => {
+                // Ensure that the message params can be deserialized.
+                let verdict: Option<bool> = deserialize_params(input);
+
+                MockSignatures(verdict);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Deploy" => {
+                // Ensure that the message params can be deserialized.
+                let (actor, constructor_params, balance): (String, RawBytes, TokenAmount) = deserialize_params(input);
+
+                Deploy(actor, constructor_params, balance);
+
+                NO_DATA_BLOCK_ID
+            },
+            "GasLimit" => {
+                // Ensure that the message params can be deserialized.
+                let gas_limit: u64 = deserialize_params(input);
+
+                GasLimit(gas_limit);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Load" => {
+                // Ensure that the message params can be deserialized.
+                let target: Address = deserialize_params(input);
+
+                Load(target);
+
+                NO_DATA_BLOCK_ID
+            },
+            "LoadBlock" => {
+                // Ensure that the message params can be deserialized.
+                let cid: Cid = deserialize_params(input);
+
+                LoadBlock(cid);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Store" => {
+                // Ensure that the message params can be deserialized.
+                let (codec, data): (u64, RawBytes) = deserialize_params(input);
+
+                Store(codec, data);
+
+                NO_DATA_BLOCK_ID
+            },
+            "AlterWith" => {
+                // Ensure that the message params can be deserialized.
+                let (address, state): (Address, RawBytes) = deserialize_params(input);
+
+                AlterWith(address, state);
+
+                NO_DATA_BLOCK_ID
+            },
+            "Fixture" => {
+                // Ensure that the message params can be deserialized.
+                let name: String = deserialize_params(input);
+
+                Fixture(name);
+
+                NO_DATA_BLOCK_ID
+            },
             _ => {
                 fvm_sdk::vm::abort(
                     ExitCode::USR_UNHANDLED_MESSAGE.value(),
@@ -124,9 +396,19 @@ fn Fee(_new_fee: fvm_shared::sys::TokenAmount) {}
 #[allow(non_snake_case)]
 fn ChainId(_new_chain_id: u64) {}
 
-/// Prank the call manager to set a pre-determined caller for the next message sent.
+/// Prank the call manager to set a pre-determined caller, and optionally origin, for the next
+/// message sent.
+#[allow(non_snake_case)]
+fn Prank(_new_caller: Address, _new_origin: Option<Address>) {}
+
+/// Prank the call manager to set a pre-determined caller, and optionally origin, for the messages
+/// sent until `StopPrank` is called.
+#[allow(non_snake_case)]
+fn StartPrank(_new_caller: Address, _new_origin: Option<Address>) {}
+
+/// Stop the prank started by `StartPrank`.
 #[allow(non_snake_case)]
-fn Prank(_new_caller: Address) {}
+fn StopPrank() {}
 
 /// Trick the call manager to set a pre-determined origin for the next message sent.
 #[allow(non_snake_case)]
@@ -136,6 +418,135 @@ fn Trick(_new_origin: Address) {}
 #[allow(non_snake_case)]
 fn Log(_message: String) {}
 
-/// Alter the state of a given actor to a new value.
+/// Alter the state of a given actor to a new value, given as a CID string.
+#[allow(non_snake_case)]
+fn Alter(_target: Address, _cid: String) {}
+
+/// Set the balance of a given actor to a new amount.
+#[allow(non_snake_case)]
+fn Deal(_target: Address, _balance: TokenAmount) {}
+
+/// Expect the next call sent by the actor to abort with a given exit code and message.
+#[allow(non_snake_case)]
+fn ExpectRevert(_exit_code: ExitCode, _message: Option<String>) {}
+
+/// Expect the next call sent by the actor to emit a given event.
+#[allow(non_snake_case)]
+fn ExpectEmit(_event: ActorEvent) {}
+
+/// Record the current state, returning the ID of the snapshot.
+#[allow(non_snake_case)]
+fn Snapshot() {}
+
+/// Restore the state recorded by a given snapshot.
+#[allow(non_snake_case)]
+fn RevertTo(_snapshot_id: u64) {}
+
+/// Mock the calls to a given actor method, returning the given bytes and exit code without running
+/// the callee.
+#[allow(non_snake_case)]
+fn MockCall(
+    _to: Address,
+    _method: MethodNum,
+    _params: Option<RawBytes>,
+    _return_bytes: RawBytes,
+    _exit_code: ExitCode,
+) {
+}
+
+/// Remove all the mocked calls.
+#[allow(non_snake_case)]
+fn ClearMockedCalls() {}
+
+/// Expect a given number of calls to an actor method to be sent during the rest of the message.
+#[allow(non_snake_case)]
+fn ExpectCall(
+    _to: Address,
+    _method: MethodNum,
+    _params: Option<RawBytes>,
+    _value: Option<TokenAmount>,
+    _count: u64,
+) {
+}
+
+/// Set the chain randomness returned for given domain separation tags and epochs.
+#[allow(non_snake_case)]
+fn SetChainRandomness(_entries: Vec<(Option<i64>, Option<ChainEpoch>, RawBytes)>) {}
+
+/// Set the beacon randomness returned for given domain separation tags and epochs.
+#[allow(non_snake_case)]
+fn SetBeaconRandomness(_entries: Vec<(Option<i64>, Option<ChainEpoch>, RawBytes)>) {}
+
+/// Set the tipset CID returned for a given epoch.
+#[allow(non_snake_case)]
+fn SetTipsetCid(_epoch: ChainEpoch, _cid: Cid) {}
+
+/// Mock the evaluation of given consensus fault evidence, returning the input target, epoch and
+/// fault type if any.
+#[allow(non_snake_case)]
+fn MockConsensusFault(
+    _h1: RawBytes,
+    _h2: RawBytes,
+    _extra: RawBytes,
+    _fault: Option<(Address, ChainEpoch, u8)>,
+) {
+}
+
+/// Answer the proof verifications as valid without verifying the proofs.
+#[allow(non_snake_case)]
+fn MockProofs(_mock_proofs: bool) {}
+
+/// Expect a proof of a given kind to be verified during the rest of the message, answering the
+/// verification with the input verdict.
+#[allow(non_snake_case)]
+fn ExpectVerifyProof(_kind: String, _verdict: bool) {}
+
+/// Create an account with a key of the input type derived from the input label, and optionally
+/// set its balance, returning its address.
+#[allow(non_snake_case)]
+fn NewAccount(_label: String, _balance: Option<TokenAmount>, _key_type: String) {}
+
+/// Create an account with the input secp256k1 private key, returning its address.
+#[allow(non_snake_case)]
+fn AddrFromKey(_key: RawBytes) {}
+
+/// Sign the input 32 bytes digest with the key of a given account, returning the signature.
+#[allow(non_snake_case)]
+fn Sign(_account: Address, _digest: RawBytes) {}
+
+/// Answer the signature verifications with the input verdict without verifying them, or verify
+/// them again if none.
+#[allow(non_snake_case)]
+fn MockSignatures(_verdict: Option<bool>) {}
+
+/// Deploy a registered actor, by name or code CID, with the input balance, calling its
+/// constructor with the input parameters, and returning its address.
+#[allow(non_snake_case)]
+fn Deploy(_actor: String, _constructor_params: RawBytes, _balance: TokenAmount) {}
+
+/// Set the gas the current message can use, the message running out of gas if it used more once
+/// the test actor returns.
+#[allow(non_snake_case)]
+fn GasLimit(_gas_limit: u64) {}
+
+/// Read the state root CID of a given actor.
+#[allow(non_snake_case)]
+fn Load(_target: Address) {}
+
+/// Read the raw bytes of a block of the blockstore by CID, such as a state root returned by
+/// `Load`.
+#[allow(non_snake_case)]
+fn LoadBlock(_cid: Cid) {}
+
+/// Put a block encoded with a given codec into the blockstore, returning its CID.
+#[allow(non_snake_case)]
+fn Store(_codec: u64, _data: RawBytes) {}
+
+/// Alter the state of a given actor to a new CBOR encoded state, putting it into the blockstore
+/// and returning its CID.
+#[allow(non_snake_case)]
+fn AlterWith(_target: Address, _state: RawBytes) {}
+
+/// Read the ID of a fixture actor deployed along with the target actor, by name.
 #[allow(non_snake_case)]
-fn Alter(_target: Address, _cid: Cid) {}
+fn Fixture(_name: String) {}
@@ -18,6 +18,9 @@ mod testing {
         "builtins-test-actor",
         "cheatcodes-test-actor",
         "fail-test-actor",
+        "fuzz-test-actor",
+        "invariant-target-actor",
+        "invariant-test-actor",
     ];
 
     const FILES_TO_WATCH: &[&str] = &["Cargo.toml", "src", "actors"];
@@ -31,7 +34,7 @@ mod testing {
             .map(Path::new)
             .map(|p| p.join("bundle"))
             .expect("no OUT_DIR env var");
-        println!("cargo:warning=out_dir: {:?}", &out_dir);
+        println!("cargo:warning=out_dir: {:?}", out_dir);
 
         let manifest_path =
             Path::new(&std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR unset"))
@@ -65,7 +68,7 @@ mod testing {
             .env_remove("CARGO_ENCODED_RUSTFLAGS");
 
         // Print out the command line we're about to run.
-        println!("cargo:warning=cmd={:?}", &cmd);
+        println!("cargo:warning=cmd={:?}", cmd);
 
         // Launch the command.
         let mut child = cmd.spawn().expect("failed to launch cargo build");
@@ -28,4 +28,9 @@ pub mod test_actors {
     pub const CHEATCODES_TEST_ACTOR_BINARY: &[u8] =
         include_bytes!(wasm_bin!("cheatcodes_test_actor"));
     pub const FAIL_TEST_ACTOR_BINARY: &[u8] = include_bytes!(wasm_bin!("fail_test_actor"));
+    pub const FUZZ_TEST_ACTOR_BINARY: &[u8] = include_bytes!(wasm_bin!("fuzz_test_actor"));
+    pub const INVARIANT_TARGET_ACTOR_BINARY: &[u8] =
+        include_bytes!(wasm_bin!("invariant_target_actor"));
+    pub const INVARIANT_TEST_ACTOR_BINARY: &[u8] =
+        include_bytes!(wasm_bin!("invariant_test_actor"));
 }
@@ -5,10 +5,11 @@ use cid::{multihash::Code, Cid};
 use frc42_dispatch::match_method;
 use fvm_ipld_blockstore::Block;
 use fvm_ipld_encoding::tuple::{Deserialize_tuple, Serialize_tuple};
-use fvm_ipld_encoding::DAG_CBOR;
+use fvm_ipld_encoding::{DAG_CBOR, IPLD_RAW};
 use fvm_sdk as sdk;
 use fvm_sdk::NO_DATA_BLOCK_ID;
 use fvm_shared::error::ExitCode;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
 use sdk::sys::ErrorNumber;
 use serde::ser;
 use thiserror::Error;
@@ -64,13 +65,13 @@ where
     Ok(sdk::ipld::put_block(DAG_CBOR, bytes.as_slice())?)
 }
 #[no_mangle]
-fn invoke(_input: u32) -> u32 {
+fn invoke(input: u32) -> u32 {
     let method_num = fvm_sdk::message::method_number();
     match_method!(
         method_num,
         {
             "Constructor" => {
-                Constructor();
+                Constructor(input);
                 NO_DATA_BLOCK_ID
             },
             "HelloWorld" => {
@@ -86,6 +87,10 @@ fn invoke(_input: u32) -> u32 {
 
                 return_ipld(&mc_origin).unwrap()
             },
+            "Emit" => {
+                Emit();
+                NO_DATA_BLOCK_ID
+            },
             _ => {
                 fvm_sdk::vm::abort(
                     ExitCode::USR_UNHANDLED_MESSAGE.value(),
@@ -98,12 +103,16 @@ fn invoke(_input: u32) -> u32 {
 
 // `Constructor` for the target actor we are using in our tests.
 #[allow(non_snake_case)]
-fn Constructor() {
+fn Constructor(input: u32) {
     // This value should always be set in the `Constrcutor`. It allows us to test that constructor
-    // for target actors are properly called.
-    let state = ActorState {
-        who_am_i: String::from("Basic Target Actor"),
-    };
+    // for target actors are properly called. It can be replaced by a name passed as parameter, to
+    // test that the configured constructor parameters are sent.
+    let who_am_i = sdk::message::params_raw(input)
+        .ok()
+        .flatten()
+        .and_then(|params| fvm_ipld_encoding::from_slice::<String>(&params.data).ok())
+        .unwrap_or_else(|| String::from("Basic Target Actor"));
+    let state = ActorState { who_am_i };
     let cid = state.save();
     fvm_sdk::sself::set_root(&cid).unwrap();
 }
@@ -114,3 +123,14 @@ fn HelloWorld() -> u32 {
 
     return_ipld(&state.who_am_i).unwrap()
 }
+
+#[allow(non_snake_case)]
+fn Emit() {
+    fvm_sdk::event::emit_event(&ActorEvent::from(vec![Entry {
+        flags: Flags::FLAG_INDEXED_ALL,
+        key: String::from("hello"),
+        codec: IPLD_RAW,
+        value: b"world".to_vec(),
+    }]))
+    .unwrap();
+}
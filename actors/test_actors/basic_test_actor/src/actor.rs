@@ -124,6 +124,10 @@ fn invoke(input: u32) -> u32 {
                 TestFailSuccess();
                 NO_DATA_BLOCK_ID
             },
+            "TestValueReceived" => {
+                TestValueReceived();
+                NO_DATA_BLOCK_ID
+            },
             _ => {
                 sdk::vm::abort(
                     ExitCode::USR_UNHANDLED_MESSAGE.value(),
@@ -212,3 +216,10 @@ fn TestFailFailed() {
 fn TestFailSuccess() {
     assert_eq!(1 + 1, 3);
 }
+
+// Tests that the value set in the ABI of a test method is sent with its message.
+#[allow(non_snake_case)]
+fn TestValueReceived() {
+    assert_eq!(sdk::message::value_received(), TokenAmount::from_atto(1000));
+    assert_eq!(sdk::sself::current_balance(), TokenAmount::from_atto(1000));
+}
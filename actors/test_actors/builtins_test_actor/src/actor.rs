@@ -46,84 +46,84 @@ fn invoke(_input: u32) -> u32 {
 fn TestBuiltinsDeployed() {
     // Test system actor deployment.
     let code_cid = get_actor_code_cid(&SYSTEM_ACTOR_ADDR)
-        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", &SYSTEM_ACTOR_ADDR));
+        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", SYSTEM_ACTOR_ADDR));
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::System as i32);
 
     // Test init actor deployment.
     let code_cid = get_actor_code_cid(&INIT_ACTOR_ADDR)
-        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", &INIT_ACTOR_ADDR));
+        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", INIT_ACTOR_ADDR));
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Init as i32);
 
     // Test reward actor deployment.
     let code_cid = get_actor_code_cid(&REWARD_ACTOR_ADDR)
-        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", &REWARD_ACTOR_ADDR));
+        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", REWARD_ACTOR_ADDR));
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Reward as i32);
 
     // Test cron actor deployment.
     let code_cid = get_actor_code_cid(&CRON_ACTOR_ADDR)
-        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", &CRON_ACTOR_ADDR));
+        .unwrap_or_else(|| panic!("Should get an code CID at address: {}", CRON_ACTOR_ADDR));
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Cron as i32);
 
     // Test power actor deployment.
     let code_cid = get_actor_code_cid(&STORAGE_POWER_ACTOR_ADDR).unwrap_or_else(|| {
         panic!(
             "Should get an code CID at address: {}",
-            &STORAGE_POWER_ACTOR_ADDR
+            STORAGE_POWER_ACTOR_ADDR
         )
     });
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Power as i32);
 
     // Test market actor deployment.
     let code_cid = get_actor_code_cid(&STORAGE_MARKET_ACTOR_ADDR).unwrap_or_else(|| {
         panic!(
             "Should get an code CID at address: {}",
-            &STORAGE_MARKET_ACTOR_ADDR
+            STORAGE_MARKET_ACTOR_ADDR
         )
     });
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Market as i32);
 
     // Test verified registry actor deployment.
     let code_cid = get_actor_code_cid(&VERIFIED_REGISTRY_ACTOR_ADDR).unwrap_or_else(|| {
         panic!(
             "Should get an code CID at address: {}",
-            &VERIFIED_REGISTRY_ACTOR_ADDR
+            VERIFIED_REGISTRY_ACTOR_ADDR
         )
     });
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::VerifiedRegistry as i32);
 
     // Test datacap actor deployment.
     let code_cid = get_actor_code_cid(&DATACAP_TOKEN_ACTOR_ADDR).unwrap_or_else(|| {
         panic!(
             "Should get an code CID at address: {}",
-            &DATACAP_TOKEN_ACTOR_ID
+            DATACAP_TOKEN_ACTOR_ID
         )
     });
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::DataCap as i32);
 
     // Test burnt funds actor deployment.
     let code_cid = get_actor_code_cid(&BURNT_FUNDS_ACTOR_ADDR).unwrap_or_else(|| {
         panic!(
             "Should get an code CID at address: {}",
-            &BURNT_FUNDS_ACTOR_ADDR
+            BURNT_FUNDS_ACTOR_ADDR
         )
     });
     let actor_type = get_builtin_actor_type(&code_cid)
-        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", &code_cid));
+        .unwrap_or_else(|| panic!("Should get a builtin actor type for CID: {}", code_cid));
     assert_eq!(actor_type, Type::Account as i32);
 }
@@ -1,20 +1,28 @@
 // Copyright 2023 Polyphene.
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use cid::multihash::Code;
+use cid::multihash::{Code, Multihash, MultihashDigest};
+use cid::Cid;
 use frc42_dispatch::{match_method, method_hash};
 use fvm_ipld_blockstore::Block;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::tuple::{Deserialize_tuple, Serialize_tuple};
-use fvm_ipld_encoding::DAG_CBOR;
 use fvm_ipld_encoding::{de::DeserializeOwned, RawBytes};
+use fvm_ipld_encoding::{DAG_CBOR, IPLD_RAW};
 use fvm_sdk as sdk;
 use fvm_sdk::NO_DATA_BLOCK_ID;
-use fvm_shared::address::Address;
+use fvm_shared::address::{Address, Protocol, BLS_PUB_LEN};
 use fvm_shared::bigint::Zero;
+use fvm_shared::crypto::signature::Signature;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
+use fvm_shared::sector::{
+    PoStProof, RegisteredPoStProof, RegisteredSealProof, SealVerifyInfo, SectorID,
+    WindowPoStVerifyInfo,
+};
 use fvm_shared::sys::SendFlags;
+use fvm_shared::{Response, IDENTITY_HASH};
 use paste::paste;
 
 macro_rules! declare_match_method {
@@ -115,6 +123,11 @@ fn invoke(input: u32) -> u32 {
         "TestFailNoParametersPrank" => TestFailNoParametersPrank,
         "TestFailAddressTypePrank" => TestFailAddressTypePrank,
         "TestPrank" => TestPrank,
+        "TestPrankOrigin" => TestPrankOrigin,
+        "TestFailDeserializationStartPrank" => TestFailDeserializationStartPrank,
+        "TestFailNoParametersStartPrank" => TestFailNoParametersStartPrank,
+        "TestFailAddressTypeStartPrank" => TestFailAddressTypeStartPrank,
+        "TestStartPrank" => TestStartPrank,
         "TestFailDeserializationTrick" => TestFailDeserializationTrick,
         "TestFailNoParametersTrick" => TestFailNoParametersTrick,
         "TestFailAddressTypeTrick" => TestFailAddressTypeTrick,
@@ -125,6 +138,112 @@ fn invoke(input: u32) -> u32 {
         "TestFailInvalidCidAlter" => TestFailInvalidCidAlter,
         "TestFailInvalidAddressAlter" => TestFailInvalidAddressAlter,
         "TestAlter" => TestAlter,
+        "TestFailDeserializationExpectRevert" => TestFailDeserializationExpectRevert,
+        "TestFailNoParametersExpectRevert" => TestFailNoParametersExpectRevert,
+        "TestFailSuccessExitCodeExpectRevert" => TestFailSuccessExitCodeExpectRevert,
+        "TestFailCallSucceededExpectRevert" => TestFailCallSucceededExpectRevert,
+        "TestFailExitCodeExpectRevert" => TestFailExitCodeExpectRevert,
+        "TestFailMessageExpectRevert" => TestFailMessageExpectRevert,
+        "TestExpectRevert" => TestExpectRevert,
+        "TestFailDeserializationDeal" => TestFailDeserializationDeal,
+        "TestFailNoParametersDeal" => TestFailNoParametersDeal,
+        "TestFailNegativeBalanceDeal" => TestFailNegativeBalanceDeal,
+        "TestFailInvalidAddressDeal" => TestFailInvalidAddressDeal,
+        "TestDeal" => TestDeal,
+        "TestDealReverted" => TestDealReverted,
+        "TestDealKept" => TestDealKept,
+        "DealAndAbort" => DealAndAbort,
+        "VerifySealAndAbort" => VerifySealAndAbort,
+        "TestFailDeserializationExpectEmit" => TestFailDeserializationExpectEmit,
+        "TestFailNoParametersExpectEmit" => TestFailNoParametersExpectEmit,
+        "TestFailEmptyEventExpectEmit" => TestFailEmptyEventExpectEmit,
+        "TestFailEventExpectEmit" => TestFailEventExpectEmit,
+        "TestFailNoEventExpectEmit" => TestFailNoEventExpectEmit,
+        "TestExpectEmit" => TestExpectEmit,
+        "TestFailDeserializationRevertTo" => TestFailDeserializationRevertTo,
+        "TestFailNoParametersRevertTo" => TestFailNoParametersRevertTo,
+        "TestFailInvalidIdRevertTo" => TestFailInvalidIdRevertTo,
+        "TestSnapshot" => TestSnapshot,
+        "TestFailDeserializationMockCall" => TestFailDeserializationMockCall,
+        "TestFailNoParametersMockCall" => TestFailNoParametersMockCall,
+        "TestMockCall" => TestMockCall,
+        "TestFailDeserializationExpectCall" => TestFailDeserializationExpectCall,
+        "TestFailNoParametersExpectCall" => TestFailNoParametersExpectCall,
+        "TestFailCountExpectCall" => TestFailCountExpectCall,
+        "TestFailValueExpectCall" => TestFailValueExpectCall,
+        "TestFailAbortExpectCall" => TestFailAbortExpectCall,
+        "TestExpectCall" => TestExpectCall,
+        "TestFailDeserializationSetChainRandomness" => TestFailDeserializationSetChainRandomness,
+        "TestFailNoParametersSetChainRandomness" => TestFailNoParametersSetChainRandomness,
+        "TestFailLengthSetChainRandomness" => TestFailLengthSetChainRandomness,
+        "TestSetChainRandomness" => TestSetChainRandomness,
+        "TestFailDeserializationSetBeaconRandomness" => TestFailDeserializationSetBeaconRandomness,
+        "TestFailNoParametersSetBeaconRandomness" => TestFailNoParametersSetBeaconRandomness,
+        "TestFailLengthSetBeaconRandomness" => TestFailLengthSetBeaconRandomness,
+        "TestSetBeaconRandomness" => TestSetBeaconRandomness,
+        "TestFailDeserializationSetTipsetCid" => TestFailDeserializationSetTipsetCid,
+        "TestFailNoParametersSetTipsetCid" => TestFailNoParametersSetTipsetCid,
+        "TestFailInvalidCidSetTipsetCid" => TestFailInvalidCidSetTipsetCid,
+        "TestSetTipsetCid" => TestSetTipsetCid,
+        "TestFailDeserializationMockConsensusFault" => TestFailDeserializationMockConsensusFault,
+        "TestFailNoParametersMockConsensusFault" => TestFailNoParametersMockConsensusFault,
+        "TestFailFaultTypeMockConsensusFault" => TestFailFaultTypeMockConsensusFault,
+        "TestFailAddressTypeMockConsensusFault" => TestFailAddressTypeMockConsensusFault,
+        "TestMockConsensusFault" => TestMockConsensusFault,
+        "TestFailDeserializationMockProofs" => TestFailDeserializationMockProofs,
+        "TestFailNoParametersMockProofs" => TestFailNoParametersMockProofs,
+        "TestMockProofs" => TestMockProofs,
+        "TestFailDeserializationExpectVerifyProof" => TestFailDeserializationExpectVerifyProof,
+        "TestFailNoParametersExpectVerifyProof" => TestFailNoParametersExpectVerifyProof,
+        "TestFailKindExpectVerifyProof" => TestFailKindExpectVerifyProof,
+        "TestFailUnverifiedExpectVerifyProof" => TestFailUnverifiedExpectVerifyProof,
+        "TestExpectVerifyProof" => TestExpectVerifyProof,
+        "TestFailDeserializationNewAccount" => TestFailDeserializationNewAccount,
+        "TestFailNoParametersNewAccount" => TestFailNoParametersNewAccount,
+        "TestNewAccount" => TestNewAccount,
+        "TestKeyTypesNewAccount" => TestKeyTypesNewAccount,
+        "TestFailKeyTypeNewAccount" => TestFailKeyTypeNewAccount,
+        "TestFailDeserializationAddrFromKey" => TestFailDeserializationAddrFromKey,
+        "TestFailNoParametersAddrFromKey" => TestFailNoParametersAddrFromKey,
+        "TestFailKeyAddrFromKey" => TestFailKeyAddrFromKey,
+        "TestAddrFromKey" => TestAddrFromKey,
+        "TestFailDeserializationSign" => TestFailDeserializationSign,
+        "TestFailNoParametersSign" => TestFailNoParametersSign,
+        "TestFailLengthSign" => TestFailLengthSign,
+        "TestFailKeySign" => TestFailKeySign,
+        "TestSign" => TestSign,
+        "TestFailDeserializationMockSignatures" => TestFailDeserializationMockSignatures,
+        "TestFailNoParametersMockSignatures" => TestFailNoParametersMockSignatures,
+        "TestMockSignatures" => TestMockSignatures,
+        "TestFailDeserializationDeploy" => TestFailDeserializationDeploy,
+        "TestFailNoParametersDeploy" => TestFailNoParametersDeploy,
+        "TestDeploy" => TestDeploy,
+        "TestDeployFailingConstructor" => TestDeployFailingConstructor,
+        "TestFailUnknownDeploy" => TestFailUnknownDeploy,
+        "TestFailDeserializationGasLimit" => TestFailDeserializationGasLimit,
+        "TestFailNoParametersGasLimit" => TestFailNoParametersGasLimit,
+        "TestGasLimit" => TestGasLimit,
+        "TestFailExceedGasLimit" => TestFailExceedGasLimit,
+        "TestFailLoopGasLimit" => TestFailLoopGasLimit,
+        "TestFailMockSignaturesGasLimit" => TestFailMockSignaturesGasLimit,
+        "TestFailDeserializationLoad" => TestFailDeserializationLoad,
+        "TestFailNoParametersLoad" => TestFailNoParametersLoad,
+        "TestLoad" => TestLoad,
+        "TestFailDeserializationLoadBlock" => TestFailDeserializationLoadBlock,
+        "TestFailNoParametersLoadBlock" => TestFailNoParametersLoadBlock,
+        "TestFailUnknownLoadBlock" => TestFailUnknownLoadBlock,
+        "TestFailUnreachableAlter" => TestFailUnreachableAlter,
+        "TestFailDeserializationStore" => TestFailDeserializationStore,
+        "TestFailNoParametersStore" => TestFailNoParametersStore,
+        "TestFailCodecStore" => TestFailCodecStore,
+        "TestStore" => TestStore,
+        "TestFailDeserializationAlterWith" => TestFailDeserializationAlterWith,
+        "TestFailNoParametersAlterWith" => TestFailNoParametersAlterWith,
+        "TestFailUnreachableAlterWith" => TestFailUnreachableAlterWith,
+        "TestAlterWith" => TestAlterWith,
+        "TestAlterWithOtherShape" => TestAlterWithOtherShape,
+        "TestFailUnknownFixture" => TestFailUnknownFixture,
+        "TestFixture" => TestFixture,
     }
 }
 
@@ -239,17 +358,35 @@ fn TestChainId(_input: u32) {
     assert_eq!(new_chain_id, nc_chain_id);
 }
 
-// Checks Prank cheatcode happy path.
-#[allow(non_snake_case)]
-fn TestPrank(input: u32) {
-    let target_actor_id: u64 = deserialize_params(input);
+// Query the caller or origin seen by the target actor.
+fn call_target_context(target_actor_id: u64, method: u64) -> u64 {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(target_actor_id),
+        method,
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
 
-    let new_caller = Address::new_id(1);
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get context from target actor")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
 
+// Send a prank cheatcode with the given parameters.
+fn prank<T: serde::Serialize>(method: u64, params: &T) {
     let res = fvm_sdk::send::send(
         &Address::new_id(98),
-        method_hash!("Prank"),
-        Some(IpldBlock::serialize(DAG_CBOR, &new_caller).unwrap()),
+        method,
+        Some(IpldBlock::serialize(DAG_CBOR, params).unwrap()),
         TokenAmount::zero(),
         None,
         SendFlags::empty(),
@@ -257,10 +394,66 @@ fn TestPrank(input: u32) {
     .unwrap();
 
     assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks Prank cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestPrank(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    let new_caller = Address::new_id(1);
+
+    prank(method_hash!("Prank"), &new_caller);
+
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(new_caller.id().unwrap(), caller);
+
+    // The prank only applies to the next call.
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(fvm_sdk::message::receiver(), caller);
+}
+
+// Checks Prank cheatcode with an origin.
+#[allow(non_snake_case)]
+fn TestPrankOrigin(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let origin = fvm_sdk::message::origin();
+
+    let new_caller = Address::new_id(1);
+    let new_origin = Address::new_id(2);
+
+    prank(method_hash!("Prank"), &(new_caller, Some(new_origin)));
+
+    let pranked_origin = call_target_context(target_actor_id, method_hash!("Origin"));
+    assert_eq!(new_origin.id().unwrap(), pranked_origin);
+
+    // The origin is restored after the pranked call.
+    let pranked_origin = call_target_context(target_actor_id, method_hash!("Origin"));
+    assert_eq!(origin, pranked_origin);
+}
+
+// Checks StartPrank and StopPrank cheatcodes happy path.
+#[allow(non_snake_case)]
+fn TestStartPrank(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let origin = fvm_sdk::message::origin();
+
+    let new_caller = Address::new_id(1);
+    let new_origin = Address::new_id(2);
+
+    prank(method_hash!("StartPrank"), &(new_caller, Some(new_origin)));
+
+    // The prank applies to every call until it is stopped.
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(new_caller.id().unwrap(), caller);
+    let pranked_origin = call_target_context(target_actor_id, method_hash!("Origin"));
+    assert_eq!(new_origin.id().unwrap(), pranked_origin);
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(new_caller.id().unwrap(), caller);
 
     let res = fvm_sdk::send::send(
-        &Address::new_id(target_actor_id),
-        method_hash!("Caller"),
+        &Address::new_id(98),
+        method_hash!("StopPrank"),
         None,
         TokenAmount::zero(),
         None,
@@ -270,15 +463,27 @@ fn TestPrank(input: u32) {
 
     assert_eq!(res.exit_code, ExitCode::OK);
 
-    let caller: u64 = RawBytes::new(
-        res.return_data
-            .expect("Should be able to get Caller from target actor")
-            .data,
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(fvm_sdk::message::receiver(), caller);
+    let pranked_origin = call_target_context(target_actor_id, method_hash!("Origin"));
+    assert_eq!(origin, pranked_origin);
+}
+
+// Checks StartPrank with a wrong address type.
+#[allow(non_snake_case)]
+fn TestFailAddressTypeStartPrank(_input: u32) {
+    let new_caller = Address::new_id(1);
+    let new_origin = Address::new_actor(b"WrongType");
+
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("StartPrank"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(new_caller, Some(new_origin))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
     )
-    .deserialize()
     .unwrap();
-
-    assert_eq!(new_caller.id().unwrap(), caller);
 }
 
 // Checks Prank with a wrong address type.
@@ -502,4 +707,1709 @@ fn TestFailInvalidCidAlter(input: u32) {
     .unwrap();
 }
 
-declare_tests_fail!("Warp", "Epoch", "Fee", "ChainId", "Prank", "Trick", "Alter");
+// Arm the ExpectRevert cheatcode for the next call.
+fn expect_revert(exit_code: ExitCode, message: Option<&str>) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ExpectRevert"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(exit_code, message)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Call a method of the target actor.
+fn call_target(target_actor_id: u64, method: u64) -> ExitCode {
+    fvm_sdk::send::send(
+        &Address::new_id(target_actor_id),
+        method,
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap()
+    .exit_code
+}
+
+// Checks ExpectRevert cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestExpectRevert(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_revert(
+        ExitCode::USR_UNHANDLED_MESSAGE,
+        Some("Unknown method number"),
+    );
+    let exit_code = call_target(target_actor_id, method_hash!("Unknown"));
+    assert_eq!(exit_code, ExitCode::OK);
+
+    // Without a message, only the exit code is checked.
+    expect_revert(ExitCode::USR_UNHANDLED_MESSAGE, None);
+    let exit_code = call_target(target_actor_id, method_hash!("Unknown"));
+    assert_eq!(exit_code, ExitCode::OK);
+
+    // The expectation only applies to the next call.
+    let exit_code = call_target(target_actor_id, method_hash!("Unknown"));
+    assert_eq!(exit_code, ExitCode::USR_UNHANDLED_MESSAGE);
+
+    // A failing syscall caught after the revert does not prevent its abort message from being
+    // checked.
+    expect_revert(
+        ExitCode::USR_UNHANDLED_MESSAGE,
+        Some("Unknown method number"),
+    );
+    let exit_code = call_target(target_actor_id, method_hash!("Unknown"));
+    assert_eq!(exit_code, ExitCode::OK);
+    assert!(fvm_sdk::send::send(
+        &Address::new_id(9999),
+        0,
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .is_err());
+}
+
+// Checks ExpectRevert with a success exit code.
+#[allow(non_snake_case)]
+fn TestFailSuccessExitCodeExpectRevert(_input: u32) {
+    expect_revert(ExitCode::OK, None);
+}
+
+// Checks ExpectRevert when the next call succeeds.
+#[allow(non_snake_case)]
+fn TestFailCallSucceededExpectRevert(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_revert(ExitCode::USR_UNHANDLED_MESSAGE, None);
+    call_target(target_actor_id, method_hash!("Caller"));
+}
+
+// Checks ExpectRevert when the next call aborts with another exit code.
+#[allow(non_snake_case)]
+fn TestFailExitCodeExpectRevert(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_revert(ExitCode::USR_ILLEGAL_ARGUMENT, None);
+    call_target(target_actor_id, method_hash!("Unknown"));
+}
+
+// Checks ExpectRevert when the next call aborts with another message.
+#[allow(non_snake_case)]
+fn TestFailMessageExpectRevert(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_revert(ExitCode::USR_UNHANDLED_MESSAGE, Some("Another message"));
+    call_target(target_actor_id, method_hash!("Unknown"));
+}
+
+// Set the balance of an actor with the Deal cheatcode.
+fn deal(target: Address, balance: TokenAmount) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Deal"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(target, balance)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks Deal cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestDeal(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let circ_supply = fvm_sdk::network::total_fil_circ_supply();
+
+    deal(
+        Address::new_id(target_actor_id),
+        TokenAmount::from_atto(1000),
+    );
+
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        TokenAmount::from_atto(1000)
+    );
+    assert_eq!(
+        fvm_sdk::network::total_fil_circ_supply(),
+        &circ_supply + TokenAmount::from_atto(1000)
+    );
+
+    // Dealt tokens can be transferred.
+    deal(
+        Address::new_id(fvm_sdk::message::receiver()),
+        TokenAmount::from_atto(1000),
+    );
+
+    let res = fvm_sdk::send::send(
+        &Address::new_id(target_actor_id),
+        0,
+        None,
+        TokenAmount::from_atto(400),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    assert_eq!(
+        fvm_sdk::sself::current_balance(),
+        TokenAmount::from_atto(600)
+    );
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        TokenAmount::from_atto(1400)
+    );
+
+    // Lowering a balance removes tokens from the circulating supply.
+    deal(Address::new_id(target_actor_id), TokenAmount::zero());
+
+    assert_eq!(
+        fvm_sdk::network::total_fil_circ_supply(),
+        &circ_supply + TokenAmount::from_atto(600)
+    );
+}
+
+// Deals tokens to the target actor then aborts, for the calls of TestDealReverted.
+#[allow(non_snake_case)]
+fn DealAndAbort(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    deal(
+        Address::new_id(target_actor_id),
+        TokenAmount::from_atto(1000),
+    );
+    fvm_sdk::vm::abort(ExitCode::USR_FORBIDDEN.value(), Some("Aborting deal"));
+}
+
+// Checks that Deal is undone, circulating supply included, when the call it was used in reverts.
+#[allow(non_snake_case)]
+fn TestDealReverted(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let balance = fvm_sdk::actor::balance_of(target_actor_id).unwrap();
+    let circ_supply = fvm_sdk::network::total_fil_circ_supply();
+
+    let res = fvm_sdk::send::send(
+        &Address::new_id(fvm_sdk::message::receiver()),
+        method_hash!("DealAndAbort"),
+        Some(IpldBlock::serialize(DAG_CBOR, &target_actor_id).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::USR_FORBIDDEN);
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        balance
+    );
+    assert_eq!(fvm_sdk::network::total_fil_circ_supply(), circ_supply);
+}
+
+// Checks that the circulating supply dealt by a previous message is kept, the target actor
+// having been dealt 1000 attoFIL.
+#[allow(non_snake_case)]
+fn TestDealKept(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        TokenAmount::from_atto(1000)
+    );
+    assert_eq!(
+        fvm_sdk::network::total_fil_circ_supply(),
+        fvm_shared::TOTAL_FILECOIN.clone() + TokenAmount::from_atto(1000)
+    );
+}
+
+// Checks Deal with a negative balance.
+#[allow(non_snake_case)]
+fn TestFailNegativeBalanceDeal(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Deal"),
+        Some(
+            IpldBlock::serialize(
+                DAG_CBOR,
+                &(Address::new_id(target_actor_id), TokenAmount::from_atto(-1)),
+            )
+            .unwrap(),
+        ),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Checks Deal with a wrong address type.
+#[allow(non_snake_case)]
+fn TestFailInvalidAddressDeal(_input: u32) {
+    let target = Address::new_actor(b"WrongType");
+
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Deal"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(target, TokenAmount::from_atto(1000))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Build an event with a single entry.
+fn event(key: &str, value: &[u8]) -> ActorEvent {
+    ActorEvent::from(vec![Entry {
+        flags: Flags::FLAG_INDEXED_ALL,
+        key: String::from(key),
+        codec: IPLD_RAW,
+        value: value.to_vec(),
+    }])
+}
+
+// Expect the next call to emit an event with the ExpectEmit cheatcode.
+fn expect_emit(event: ActorEvent) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ExpectEmit"),
+        Some(IpldBlock::serialize(DAG_CBOR, &event).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks ExpectEmit cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestExpectEmit(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_emit(event("hello", b"world"));
+    let exit_code = call_target(target_actor_id, method_hash!("Emit"));
+    assert_eq!(exit_code, ExitCode::OK);
+
+    // Expectations only apply to the next call.
+    let exit_code = call_target(target_actor_id, method_hash!("Caller"));
+    assert_eq!(exit_code, ExitCode::OK);
+}
+
+// Checks ExpectEmit with an event without entries.
+#[allow(non_snake_case)]
+fn TestFailEmptyEventExpectEmit(_input: u32) {
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ExpectEmit"),
+        Some(IpldBlock::serialize(DAG_CBOR, &ActorEvent::from(vec![])).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Checks ExpectEmit when the next call emits another event.
+#[allow(non_snake_case)]
+fn TestFailEventExpectEmit(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_emit(event("hello", b"kythera"));
+    call_target(target_actor_id, method_hash!("Emit"));
+}
+
+// Checks ExpectEmit when the next call emits no event.
+#[allow(non_snake_case)]
+fn TestFailNoEventExpectEmit(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_emit(event("hello", b"world"));
+    call_target(target_actor_id, method_hash!("Caller"));
+}
+
+// Record the current state with the Snapshot cheatcode.
+fn snapshot() -> u64 {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Snapshot"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get snapshot ID from Snapshot cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+// Restore a recorded state with the RevertTo cheatcode.
+fn revert_to(snapshot_id: u64) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("RevertTo"),
+        Some(IpldBlock::serialize(DAG_CBOR, &snapshot_id).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks Snapshot and RevertTo cheatcodes happy path.
+#[allow(non_snake_case)]
+fn TestSnapshot(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let balance = fvm_sdk::actor::balance_of(target_actor_id).unwrap();
+    let circ_supply = fvm_sdk::network::total_fil_circ_supply();
+
+    let first_snapshot = snapshot();
+    deal(
+        Address::new_id(target_actor_id),
+        TokenAmount::from_atto(1000),
+    );
+
+    let second_snapshot = snapshot();
+    assert_eq!(second_snapshot, first_snapshot + 1);
+    deal(
+        Address::new_id(target_actor_id),
+        TokenAmount::from_atto(2000),
+    );
+
+    revert_to(second_snapshot);
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        TokenAmount::from_atto(1000)
+    );
+
+    revert_to(first_snapshot);
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        balance
+    );
+    assert_eq!(fvm_sdk::network::total_fil_circ_supply(), circ_supply);
+
+    // Snapshots can be reverted to several times.
+    deal(
+        Address::new_id(target_actor_id),
+        TokenAmount::from_atto(3000),
+    );
+    revert_to(second_snapshot);
+    assert_eq!(
+        fvm_sdk::actor::balance_of(target_actor_id).unwrap(),
+        TokenAmount::from_atto(1000)
+    );
+}
+
+// Checks RevertTo with an unknown snapshot ID.
+#[allow(non_snake_case)]
+fn TestFailInvalidIdRevertTo(_input: u32) {
+    let snapshot_id = snapshot();
+
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("RevertTo"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(snapshot_id + 1)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Mock calls with the MockCall cheatcode.
+fn mock_call(
+    to: Address,
+    method: u64,
+    params: Option<RawBytes>,
+    return_bytes: RawBytes,
+    exit_code: ExitCode,
+) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("MockCall"),
+        Some(
+            IpldBlock::serialize(DAG_CBOR, &(to, method, params, return_bytes, exit_code)).unwrap(),
+        ),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks MockCall and ClearMockedCalls cheatcodes happy path.
+#[allow(non_snake_case)]
+fn TestMockCall(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    // Calls to actors that are not deployed can be mocked.
+    let unknown_actor = Address::new_secp256k1(&[1; 65]).unwrap();
+    mock_call(
+        unknown_actor,
+        method_hash!("Balance"),
+        None,
+        RawBytes::serialize(42u64).unwrap(),
+        ExitCode::OK,
+    );
+    let res = fvm_sdk::send::send(
+        &unknown_actor,
+        method_hash!("Balance"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+    let balance: u64 = RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap();
+    assert_eq!(balance, 42);
+
+    // Mocked calls are answered without running the callee.
+    mock_call(
+        Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        None,
+        RawBytes::serialize(7u64).unwrap(),
+        ExitCode::OK,
+    );
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(caller, 7);
+
+    // Mocks with matching parameters take precedence.
+    let params = RawBytes::serialize("params").unwrap();
+    mock_call(
+        Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        Some(params.clone()),
+        RawBytes::default(),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    let res = fvm_sdk::send::send(
+        &Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        Some(IpldBlock {
+            codec: DAG_CBOR,
+            data: params.bytes().to_vec(),
+        }),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::USR_ILLEGAL_ARGUMENT);
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(caller, 7);
+
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ClearMockedCalls"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    let caller = call_target_context(target_actor_id, method_hash!("Caller"));
+    assert_eq!(caller, fvm_sdk::message::receiver());
+}
+
+// Expect calls with the ExpectCall cheatcode.
+fn expect_call(
+    to: Address,
+    method: u64,
+    params: Option<RawBytes>,
+    value: Option<TokenAmount>,
+    count: u64,
+) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ExpectCall"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(to, method, params, value, count)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks ExpectCall cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestExpectCall(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let target = Address::new_id(target_actor_id);
+
+    expect_call(target, method_hash!("Caller"), None, None, 2);
+    expect_call(
+        target,
+        method_hash!("Origin"),
+        None,
+        Some(TokenAmount::zero()),
+        1,
+    );
+    expect_call(target, method_hash!("HelloWorld"), None, None, 0);
+
+    call_target(target_actor_id, method_hash!("Caller"));
+    call_target(target_actor_id, method_hash!("Origin"));
+    call_target(target_actor_id, method_hash!("Caller"));
+}
+
+// Checks ExpectCall when fewer calls are sent.
+#[allow(non_snake_case)]
+fn TestFailCountExpectCall(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_call(
+        Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        None,
+        None,
+        2,
+    );
+    call_target(target_actor_id, method_hash!("Caller"));
+}
+
+// Checks ExpectCall when the call transfers another value.
+#[allow(non_snake_case)]
+fn TestFailValueExpectCall(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_call(
+        Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        None,
+        Some(TokenAmount::from_atto(1)),
+        1,
+    );
+    call_target(target_actor_id, method_hash!("Caller"));
+}
+
+// Checks ExpectCall when the test aborts after the call, reverting it.
+#[allow(non_snake_case)]
+fn TestFailAbortExpectCall(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    expect_call(
+        Address::new_id(target_actor_id),
+        method_hash!("Caller"),
+        None,
+        None,
+        1,
+    );
+    call_target(target_actor_id, method_hash!("Caller"));
+    fvm_sdk::vm::abort(ExitCode::USR_FORBIDDEN.value(), Some("Aborting test"));
+}
+
+// Set randomness with the SetChainRandomness or SetBeaconRandomness cheatcode.
+fn set_randomness<S: serde::Serialize>(cheatcode: u64, params: &S) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        cheatcode,
+        Some(IpldBlock::serialize(DAG_CBOR, params).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Set the current epoch with the Epoch cheatcode.
+fn set_epoch(epoch: i64) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Epoch"),
+        Some(IpldBlock::serialize(DAG_CBOR, &epoch).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks SetChainRandomness cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestSetChainRandomness(_input: u32) {
+    set_epoch(20);
+
+    // Default externs return zero randomness.
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(1, 0, &[]).unwrap(),
+        [0u8; 32]
+    );
+
+    // A fixed randomness is returned for any tag and epoch.
+    set_randomness(
+        method_hash!("SetChainRandomness"),
+        &RawBytes::new(vec![1u8; 32]),
+    );
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(1, 0, &[]).unwrap(),
+        [1u8; 32]
+    );
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(2, 10, b"entropy").unwrap(),
+        [1u8; 32]
+    );
+
+    // The most specific entry of a table is returned.
+    let entries: Vec<(Option<i64>, Option<i64>, RawBytes)> = vec![
+        (None, None, RawBytes::new(vec![2u8; 32])),
+        (Some(1), Some(10), RawBytes::new(vec![3u8; 32])),
+        (None, Some(20), RawBytes::new(vec![4u8; 32])),
+    ];
+    set_randomness(method_hash!("SetChainRandomness"), &entries);
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(1, 10, &[]).unwrap(),
+        [3u8; 32]
+    );
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(2, 10, &[]).unwrap(),
+        [2u8; 32]
+    );
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(1, 20, &[]).unwrap(),
+        [4u8; 32]
+    );
+    // Randomness still cannot be drawn from a future or negative epoch.
+    assert!(fvm_sdk::rand::get_chain_randomness(1, 21, &[]).is_err());
+    assert!(fvm_sdk::rand::get_chain_randomness(1, -1, &[]).is_err());
+
+    // Beacon randomness is not affected.
+    assert_eq!(
+        fvm_sdk::rand::get_beacon_randomness(1, 0, &[]).unwrap(),
+        [0u8; 32]
+    );
+}
+
+// Checks SetChainRandomness with a randomness of the wrong length.
+#[allow(non_snake_case)]
+fn TestFailLengthSetChainRandomness(_input: u32) {
+    set_randomness(
+        method_hash!("SetChainRandomness"),
+        &RawBytes::new(vec![1u8; 31]),
+    );
+}
+
+// Checks SetBeaconRandomness cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestSetBeaconRandomness(_input: u32) {
+    set_epoch(10);
+
+    let entries: Vec<(Option<i64>, Option<i64>, RawBytes)> = vec![
+        (Some(1), None, RawBytes::new(vec![5u8; 32])),
+        (Some(1), Some(0), RawBytes::new(vec![6u8; 32])),
+    ];
+    set_randomness(method_hash!("SetBeaconRandomness"), &entries);
+
+    assert_eq!(
+        fvm_sdk::rand::get_beacon_randomness(1, 0, &[]).unwrap(),
+        [6u8; 32]
+    );
+    assert_eq!(
+        fvm_sdk::rand::get_beacon_randomness(1, 10, &[]).unwrap(),
+        [5u8; 32]
+    );
+    // Tags without entries fall back to the externs.
+    assert_eq!(
+        fvm_sdk::rand::get_beacon_randomness(2, 0, &[]).unwrap(),
+        [0u8; 32]
+    );
+    assert!(fvm_sdk::rand::get_beacon_randomness(1, 11, &[]).is_err());
+
+    // Chain randomness is not affected.
+    assert_eq!(
+        fvm_sdk::rand::get_chain_randomness(1, 0, &[]).unwrap(),
+        [0u8; 32]
+    );
+}
+
+// Checks SetBeaconRandomness with a randomness of the wrong length.
+#[allow(non_snake_case)]
+fn TestFailLengthSetBeaconRandomness(_input: u32) {
+    set_randomness(
+        method_hash!("SetBeaconRandomness"),
+        &RawBytes::new(vec![1u8; 33]),
+    );
+}
+
+// Checks SetTipsetCid cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestSetTipsetCid(_input: u32) {
+    set_epoch(10);
+
+    // Default externs return an identity-hash CID of the epoch.
+    let default_cid = Cid::new_v1(
+        DAG_CBOR,
+        Multihash::wrap(IDENTITY_HASH, &5i64.to_be_bytes()).unwrap(),
+    );
+    assert_eq!(
+        fvm_sdk::network::tipset_cid(5).unwrap().to_string(),
+        default_cid.to_string()
+    );
+
+    let cid = Cid::new_v1(DAG_CBOR, Code::Blake2b256.digest(b"tipset"));
+    set_tipset_cid(5, &cid);
+    assert_eq!(
+        fvm_sdk::network::tipset_cid(5).unwrap().to_string(),
+        cid.to_string()
+    );
+    assert_ne!(
+        fvm_sdk::network::tipset_cid(6).unwrap().to_string(),
+        cid.to_string()
+    );
+
+    // The current epoch still cannot be looked up, even when set.
+    set_tipset_cid(10, &cid);
+    assert!(fvm_sdk::network::tipset_cid(10).is_err());
+}
+
+// Set the tipset CID of an epoch with the SetTipsetCid cheatcode.
+fn set_tipset_cid(epoch: i64, cid: &Cid) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("SetTipsetCid"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(epoch, cid.to_string())).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks SetTipsetCid with an invalid CID.
+#[allow(non_snake_case)]
+fn TestFailInvalidCidSetTipsetCid(_input: u32) {
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("SetTipsetCid"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(5i64, String::from("azertyuiop"))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Mock consensus fault evidence with the MockConsensusFault cheatcode.
+fn mock_consensus_fault(h1: &[u8], h2: &[u8], fault: Option<(Address, i64, u8)>) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("MockConsensusFault"),
+        Some(
+            IpldBlock::serialize(
+                DAG_CBOR,
+                &(
+                    RawBytes::new(h1.to_vec()),
+                    RawBytes::new(h2.to_vec()),
+                    RawBytes::default(),
+                    fault,
+                ),
+            )
+            .unwrap(),
+        ),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks MockConsensusFault cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestMockConsensusFault(_input: u32) {
+    // Default externs evaluate no fault.
+    assert!(fvm_sdk::crypto::verify_consensus_fault(b"h1", b"h2", &[])
+        .unwrap()
+        .is_none());
+
+    mock_consensus_fault(b"h1", b"h2", Some((Address::new_id(1000), 5, 1)));
+
+    let fault = fvm_sdk::crypto::verify_consensus_fault(b"h1", b"h2", &[])
+        .unwrap()
+        .unwrap();
+    assert_eq!(fault.target, Address::new_id(1000));
+    assert_eq!(fault.epoch, 5);
+    assert_eq!(fault.fault_type as u8, 1);
+
+    // Other evidence is not affected.
+    assert!(fvm_sdk::crypto::verify_consensus_fault(b"h1", b"h3", &[])
+        .unwrap()
+        .is_none());
+
+    // A new mock replaces the one with the same evidence.
+    mock_consensus_fault(b"h1", b"h2", None);
+    assert!(fvm_sdk::crypto::verify_consensus_fault(b"h1", b"h2", &[])
+        .unwrap()
+        .is_none());
+}
+
+// Checks MockConsensusFault with an unknown fault type.
+#[allow(non_snake_case)]
+fn TestFailFaultTypeMockConsensusFault(_input: u32) {
+    mock_consensus_fault(b"h1", b"h2", Some((Address::new_id(1000), 5, 4)));
+}
+
+// Checks MockConsensusFault with a wrong target address type.
+#[allow(non_snake_case)]
+fn TestFailAddressTypeMockConsensusFault(_input: u32) {
+    mock_consensus_fault(b"h1", b"h2", Some((Address::new_actor(b"WrongType"), 5, 1)));
+}
+
+// Answer the proof verifications as valid with the MockProofs cheatcode.
+fn mock_proofs(mock_proofs: bool) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("MockProofs"),
+        Some(IpldBlock::serialize(DAG_CBOR, &mock_proofs).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Expect a proof verification with the ExpectVerifyProof cheatcode.
+fn expect_verify_proof(kind: &str, verdict: bool) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("ExpectVerifyProof"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(kind, verdict)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Seal verification info with an invalid proof.
+fn seal_verify_info() -> SealVerifyInfo {
+    let cid = fvm_sdk::ipld::put(Code::Blake2b256.into(), 32, DAG_CBOR, b"sector").unwrap();
+
+    SealVerifyInfo {
+        registered_proof: RegisteredSealProof::StackedDRG2KiBV1P1,
+        sector_id: SectorID {
+            miner: 1000,
+            number: 1,
+        },
+        deal_ids: vec![],
+        randomness: Default::default(),
+        interactive_randomness: Default::default(),
+        proof: vec![0u8; 32],
+        sealed_cid: cid,
+        unsealed_cid: cid,
+    }
+}
+
+// Window PoSt verification info with an invalid proof.
+fn window_post_verify_info() -> WindowPoStVerifyInfo {
+    WindowPoStVerifyInfo {
+        randomness: Default::default(),
+        proofs: vec![PoStProof {
+            post_proof: RegisteredPoStProof::StackedDRGWindow2KiBV1,
+            proof_bytes: vec![0u8; 32],
+        }],
+        challenged_sectors: vec![],
+        prover: 1000,
+    }
+}
+
+// Checks MockProofs cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestMockProofs(_input: u32) {
+    mock_proofs(true);
+
+    assert!(fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+    assert!(fvm_sdk::crypto::verify_post(&window_post_verify_info()).unwrap());
+    assert_eq!(
+        fvm_sdk::crypto::batch_verify_seals(&[seal_verify_info(), seal_verify_info()]).unwrap(),
+        vec![true, true]
+    );
+}
+
+// Checks ExpectVerifyProof cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestExpectVerifyProof(_input: u32) {
+    mock_proofs(true);
+    expect_verify_proof("Seal", false);
+    expect_verify_proof("Post", false);
+    expect_verify_proof("Seal", false);
+
+    assert!(!fvm_sdk::crypto::verify_post(&window_post_verify_info()).unwrap());
+    assert!(!fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+    // Each seal of a batch consumes an expected seal verification.
+    assert_eq!(
+        fvm_sdk::crypto::batch_verify_seals(&[seal_verify_info(), seal_verify_info()]).unwrap(),
+        vec![false, true]
+    );
+    assert!(fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+
+    // Expected verifications are answered even if the proofs are not mocked.
+    mock_proofs(false);
+    expect_verify_proof("Post", true);
+    assert!(fvm_sdk::crypto::verify_post(&window_post_verify_info()).unwrap());
+
+    // Verifications made by a reverted call are expected again.
+    expect_verify_proof("Seal", true);
+    let res = fvm_sdk::send::send(
+        &Address::new_id(fvm_sdk::message::receiver()),
+        method_hash!("VerifySealAndAbort"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::USR_FORBIDDEN);
+    assert!(fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+}
+
+// Verifies a seal then aborts, for the calls of TestExpectVerifyProof.
+#[allow(non_snake_case)]
+fn VerifySealAndAbort(_input: u32) {
+    assert!(fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+    fvm_sdk::vm::abort(
+        ExitCode::USR_FORBIDDEN.value(),
+        Some("Aborting verification"),
+    );
+}
+
+// Checks ExpectVerifyProof with an unknown proof kind.
+#[allow(non_snake_case)]
+fn TestFailKindExpectVerifyProof(_input: u32) {
+    expect_verify_proof("WinningPost", true);
+}
+
+// Checks ExpectVerifyProof when the proof is not verified.
+#[allow(non_snake_case)]
+fn TestFailUnverifiedExpectVerifyProof(_input: u32) {
+    expect_verify_proof("Seal", true);
+    expect_verify_proof("ReplicaUpdate", true);
+
+    assert!(fvm_sdk::crypto::verify_seal(&seal_verify_info()).unwrap());
+}
+
+// Create a keyed account with the NewAccount cheatcode.
+fn new_account(label: &str) -> Address {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("NewAccount"),
+        Some(IpldBlock::serialize(DAG_CBOR, &label).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get address from NewAccount cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+// Create a keyed account of a given key type and balance with the NewAccount cheatcode.
+fn new_typed_account(label: &str, balance: TokenAmount, key_type: &str) -> Address {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("NewAccount"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(label, Some(balance), key_type)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get address from NewAccount cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+// Create a keyed account with the AddrFromKey cheatcode.
+fn addr_from_key(key: &[u8]) -> Address {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("AddrFromKey"),
+        Some(IpldBlock::serialize(DAG_CBOR, &RawBytes::new(key.to_vec())).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get address from AddrFromKey cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+// Sign a digest with the Sign cheatcode.
+fn sign(account: &Address, digest: &[u8]) -> Vec<u8> {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Sign"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(account, RawBytes::new(digest.to_vec()))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    let signature: RawBytes = RawBytes::new(
+        res.return_data
+            .expect("Should be able to get signature from Sign cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap();
+    signature.into()
+}
+
+// Set the signature verifications verdict with the MockSignatures cheatcode.
+fn mock_signatures(verdict: Option<bool>) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("MockSignatures"),
+        Some(IpldBlock::serialize(DAG_CBOR, &verdict).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Verify a secp256k1 signature of a plaintext.
+fn verify_signature(signature: Vec<u8>, signer: &Address, plaintext: &[u8]) -> bool {
+    fvm_sdk::crypto::verify_signature(&Signature::new_secp256k1(signature), signer, plaintext)
+        .unwrap()
+}
+
+// Checks NewAccount cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestNewAccount(_input: u32) {
+    let alice = new_account("alice");
+    let bob = new_account("bob");
+
+    // Accounts are derived from their label.
+    assert_ne!(alice, bob);
+    assert_eq!(new_account("alice"), alice);
+    assert!(fvm_sdk::actor::resolve_address(&alice).is_some());
+    assert!(fvm_sdk::actor::resolve_address(&bob).is_some());
+}
+
+// Builtin actor type of the Ethereum accounts, unknown to the runtime version used.
+const ETH_ACCOUNT_TYPE: i32 = 16;
+
+// Checks NewAccount cheatcode with key types and balances.
+#[allow(non_snake_case)]
+fn TestKeyTypesNewAccount(_input: u32) {
+    let carol = new_typed_account("carol", TokenAmount::from_atto(100), "Bls");
+    assert_eq!(carol.protocol(), Protocol::BLS);
+    let carol_id = fvm_sdk::actor::resolve_address(&carol).unwrap();
+    assert_eq!(
+        fvm_sdk::actor::balance_of(carol_id).unwrap(),
+        TokenAmount::from_atto(100)
+    );
+
+    // BLS accounts sign the plaintext itself.
+    let plaintext = b"Kythera";
+    let signature = sign(&carol, plaintext);
+    assert!(
+        fvm_sdk::crypto::verify_signature(&Signature::new_bls(signature), &carol, plaintext)
+            .unwrap()
+    );
+
+    let dave = new_typed_account("dave", TokenAmount::from_atto(200), "Delegated");
+    assert_eq!(dave.protocol(), Protocol::Delegated);
+    let dave_id = fvm_sdk::actor::resolve_address(&dave).unwrap();
+    let code = fvm_sdk::actor::get_actor_code_cid(&Address::new_id(dave_id)).unwrap();
+    assert_eq!(
+        fvm_sdk::actor::get_builtin_actor_type(&code),
+        Some(ETH_ACCOUNT_TYPE)
+    );
+    assert_eq!(
+        fvm_sdk::actor::balance_of(dave_id).unwrap(),
+        TokenAmount::from_atto(200)
+    );
+
+    // The balance of an existing account is set again.
+    new_typed_account("dave", TokenAmount::from_atto(300), "Delegated");
+    assert_eq!(
+        fvm_sdk::actor::balance_of(dave_id).unwrap(),
+        TokenAmount::from_atto(300)
+    );
+}
+
+// Checks NewAccount with an unknown key type.
+#[allow(non_snake_case)]
+fn TestFailKeyTypeNewAccount(_input: u32) {
+    new_typed_account("erin", TokenAmount::zero(), "Ed25519");
+}
+
+// Checks AddrFromKey cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestAddrFromKey(_input: u32) {
+    let address = addr_from_key(&[1u8; 32]);
+
+    assert_eq!(addr_from_key(&[1u8; 32]), address);
+    assert!(fvm_sdk::actor::resolve_address(&address).is_some());
+
+    let plaintext = b"Kythera";
+    let signature = sign(&address, &fvm_sdk::crypto::hash_blake2b(plaintext));
+    assert!(verify_signature(signature, &address, plaintext));
+}
+
+// Checks AddrFromKey with an invalid private key.
+#[allow(non_snake_case)]
+fn TestFailKeyAddrFromKey(_input: u32) {
+    addr_from_key(&[0u8; 32]);
+}
+
+// Checks Sign cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestSign(_input: u32) {
+    let plaintext = b"Kythera";
+    let digest = fvm_sdk::crypto::hash_blake2b(plaintext);
+
+    let alice = new_account("alice");
+    let signature = sign(&alice, &digest);
+    assert!(verify_signature(signature.clone(), &alice, plaintext));
+    assert!(!verify_signature(signature, &new_account("bob"), plaintext));
+
+    // ID addresses are resolved to the key of the account.
+    let alice_id = Address::new_id(fvm_sdk::actor::resolve_address(&alice).unwrap());
+    assert!(verify_signature(
+        sign(&alice_id, &digest),
+        &alice,
+        plaintext
+    ));
+
+    // The account sending the test messages is keyed.
+    sign(&Address::new_id(fvm_sdk::message::origin()), &digest);
+}
+
+// Checks Sign with a digest of the wrong length.
+#[allow(non_snake_case)]
+fn TestFailLengthSign(_input: u32) {
+    sign(&new_account("alice"), &[0u8; 31]);
+}
+
+// Checks Sign with an account whose key is not managed.
+#[allow(non_snake_case)]
+fn TestFailKeySign(_input: u32) {
+    sign(&Address::new_id(98), &[0u8; 32]);
+}
+
+// Checks MockSignatures cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestMockSignatures(_input: u32) {
+    let plaintext = b"Kythera";
+    let alice = new_account("alice");
+    let signature = sign(&alice, &fvm_sdk::crypto::hash_blake2b(plaintext));
+
+    mock_signatures(Some(false));
+    assert!(!verify_signature(signature.clone(), &alice, plaintext));
+
+    mock_signatures(Some(true));
+    assert!(verify_signature(vec![0u8; 65], &alice, plaintext));
+
+    mock_signatures(None);
+    assert!(verify_signature(signature, &alice, plaintext));
+    assert!(!verify_signature(vec![0u8; 65], &alice, plaintext));
+}
+
+// Call the Deploy cheatcode for a registered actor.
+fn send_deploy(actor: &str, balance: TokenAmount) -> Response {
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Deploy"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(actor, RawBytes::default(), balance)).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap()
+}
+
+// Deploy a registered actor with the Deploy cheatcode.
+fn deploy(actor: &str, balance: TokenAmount) -> Address {
+    let res = send_deploy(actor, balance);
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get address from Deploy cheatcode")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+// Checks Deploy cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestDeploy(_input: u32) {
+    let first = deploy("Target.wasm", TokenAmount::from_atto(100));
+    let second = deploy("Target.wasm", TokenAmount::zero());
+
+    // Every deployment is a new actor.
+    assert_ne!(first, second);
+    assert_eq!(
+        fvm_sdk::actor::balance_of(first.id().unwrap()).unwrap(),
+        TokenAmount::from_atto(100)
+    );
+
+    // The constructor of the deployed actor has been called.
+    let res = fvm_sdk::send::send(
+        &first,
+        method_hash!("HelloWorld"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+    let who_am_i: String = RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap();
+    assert_eq!(who_am_i, String::from("Basic Target Actor"));
+
+    // Actors can also be deployed by code CID.
+    let code = fvm_sdk::actor::get_actor_code_cid(&first).unwrap();
+    let third = deploy(&code.to_string(), TokenAmount::zero());
+    assert_eq!(fvm_sdk::actor::get_actor_code_cid(&third), Some(code));
+}
+
+// Checks Deploy with an actor whose constructor fails.
+#[allow(non_snake_case)]
+fn TestDeployFailingConstructor(_input: u32) {
+    // The deployment aborts with the exit code of the constructor.
+    let res = send_deploy("Failing.wasm", TokenAmount::zero());
+    assert_eq!(res.exit_code, ExitCode::SYS_ILLEGAL_INSTRUCTION);
+
+    // The failing deployment can be expected like a reverting call.
+    expect_revert(ExitCode::SYS_ILLEGAL_INSTRUCTION, None);
+    let res = send_deploy("Failing.wasm", TokenAmount::zero());
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks Deploy with an actor that is not registered.
+#[allow(non_snake_case)]
+fn TestFailUnknownDeploy(_input: u32) {
+    deploy("Unknown.wasm", TokenAmount::zero());
+}
+
+// Set the gas the message can use with the GasLimit cheatcode.
+fn gas_limit(gas_limit: u64) {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("GasLimit"),
+        Some(IpldBlock::serialize(DAG_CBOR, &gas_limit).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+}
+
+// Checks GasLimit cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestGasLimit(_input: u32) {
+    gas_limit(100_000_000);
+
+    // The last limit set applies.
+    gas_limit(u64::MAX);
+    gas_limit(100_000_000);
+}
+
+// Checks that a message using more gas than the GasLimit runs out of gas.
+#[allow(non_snake_case)]
+fn TestFailExceedGasLimit(_input: u32) {
+    gas_limit(1);
+}
+
+// Checks that the GasLimit is enforced while the test actor runs.
+#[allow(non_snake_case)]
+fn TestFailLoopGasLimit(_input: u32) {
+    gas_limit(10_000_000);
+
+    let mut i: u64 = 0;
+    loop {
+        i = std::hint::black_box(i.wrapping_add(1));
+    }
+}
+
+// Checks that the GasLimit is enforced on the mocked signature verifications.
+#[allow(non_snake_case)]
+fn TestFailMockSignaturesGasLimit(_input: u32) {
+    mock_signatures(Some(true));
+
+    // Leave the message with enough gas to send the cheatcode, but not to verify a BLS
+    // signature.
+    gas_limit(1_000_000_000);
+    let gas_used = 1_000_000_000 - fvm_sdk::gas::available();
+    gas_limit(gas_used + 5_000_000);
+
+    let signer = Address::new_bls(&[0u8; BLS_PUB_LEN]).unwrap();
+    fvm_sdk::crypto::verify_signature(&Signature::new_bls(vec![0u8; 96]), &signer, b"Kythera")
+        .unwrap();
+
+    // Not reached, raising the limit would let the test succeed.
+    gas_limit(1_000_000_000);
+}
+
+// Read the state root CID of an actor with the Load cheatcode.
+fn load(target: Address) -> Cid {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Load"),
+        Some(IpldBlock::serialize(DAG_CBOR, &target).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap()
+}
+
+// Read the raw bytes of a block with the LoadBlock cheatcode.
+fn load_block(cid: &Cid) -> Vec<u8> {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("LoadBlock"),
+        Some(IpldBlock::serialize(DAG_CBOR, &cid).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    let block: RawBytes = RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap();
+    block.to_vec()
+}
+
+// Checks Load and LoadBlock cheatcodes happy path.
+#[allow(non_snake_case)]
+fn TestLoad(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let target = Address::new_id(target_actor_id);
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    struct TargetState {
+        who_am_i: String,
+    }
+
+    // The state set by the constructor of the target actor can be read.
+    let cid = load(target);
+    let state: TargetState = fvm_ipld_encoding::from_slice(&load_block(&cid)).unwrap();
+    assert_eq!(state.who_am_i, String::from("Basic Target Actor"));
+
+    // The state set with Alter is read back.
+    let new_state = TargetState {
+        who_am_i: String::from("I am new value"),
+    };
+    let new_cid = fvm_sdk::ipld::put(
+        Code::Blake2b256.into(),
+        32,
+        DAG_CBOR,
+        &fvm_ipld_encoding::to_vec(&new_state).unwrap(),
+    )
+    .unwrap();
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Alter"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(target, new_cid.to_string())).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    assert_eq!(load(target), new_cid);
+    let state: TargetState = fvm_ipld_encoding::from_slice(&load_block(&new_cid)).unwrap();
+    assert_eq!(state.who_am_i, new_state.who_am_i);
+}
+
+// Checks LoadBlock with a block that is not in the blockstore.
+#[allow(non_snake_case)]
+fn TestFailUnknownLoadBlock(_input: u32) {
+    let cid = Cid::new_v1(DAG_CBOR, Code::Blake2b256.digest(b"unknown block"));
+    load_block(&cid);
+}
+
+// Checks Alter with a state root that is not in the blockstore.
+#[allow(non_snake_case)]
+fn TestFailUnreachableAlter(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let cid = Cid::new_v1(DAG_CBOR, Code::Blake2b256.digest(b"unknown block"));
+
+    fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Alter"),
+        Some(
+            IpldBlock::serialize(
+                DAG_CBOR,
+                &(Address::new_id(target_actor_id), cid.to_string()),
+            )
+            .unwrap(),
+        ),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+}
+
+// Put a block into the blockstore with the Store cheatcode.
+fn store(codec: u64, data: &[u8]) -> Cid {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Store"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(codec, RawBytes::from(data.to_vec()))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap()
+}
+
+// Alter the state of an actor to the input CBOR encoded state with the AlterWith cheatcode.
+fn alter_with(target: Address, state: Vec<u8>) -> Cid {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("AlterWith"),
+        Some(IpldBlock::serialize(DAG_CBOR, &(target, RawBytes::new(state))).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap()
+}
+
+// Checks Store cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestStore(_input: u32) {
+    let data = b"raw block".to_vec();
+    let cid = store(IPLD_RAW, &data);
+    assert_eq!(cid, Cid::new_v1(IPLD_RAW, Code::Blake2b256.digest(&data)));
+    assert_eq!(load_block(&cid), data);
+
+    let data = fvm_ipld_encoding::to_vec(&(1u64, String::from("cbor block"))).unwrap();
+    let cid = store(DAG_CBOR, &data);
+    assert_eq!(load_block(&cid), data);
+}
+
+// Checks Store with a codec that is not supported.
+#[allow(non_snake_case)]
+fn TestFailCodecStore(_input: u32) {
+    // DAG-PB codec.
+    store(0x70, b"block");
+}
+
+// Checks AlterWith cheatcode happy path.
+#[allow(non_snake_case)]
+fn TestAlterWith(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let target = Address::new_id(target_actor_id);
+
+    #[derive(Serialize_tuple)]
+    struct TargetState {
+        who_am_i: String,
+    }
+
+    let new_state = TargetState {
+        who_am_i: String::from("I am new value"),
+    };
+    let cid = alter_with(target, fvm_ipld_encoding::to_vec(&new_state).unwrap());
+    assert_eq!(load(target), cid);
+
+    let res = fvm_sdk::send::send(
+        &target,
+        method_hash!("HelloWorld"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+    let who_am_i: String = RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap();
+    assert_eq!(who_am_i, new_state.who_am_i);
+}
+
+// Checks AlterWith with a state that does not have the shape of the current one, which only
+// logs a warning.
+#[allow(non_snake_case)]
+fn TestAlterWithOtherShape(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let target = Address::new_id(target_actor_id);
+
+    let cid = alter_with(target, fvm_ipld_encoding::to_vec(&(1u64, 2u64)).unwrap());
+    assert_eq!(load(target), cid);
+}
+
+// Checks AlterWith with a state linking to a block that is not in the blockstore.
+#[allow(non_snake_case)]
+fn TestFailUnreachableAlterWith(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let cid = Cid::new_v1(DAG_CBOR, Code::Blake2b256.digest(b"unknown block"));
+
+    // A list holding the link, a CID being encoded as tag 42 on its bytes prefixed by a 0.
+    let mut link = vec![0];
+    link.extend(cid.to_bytes());
+    let mut state = vec![0x81, 0xd8, 0x2a, 0x58, link.len() as u8];
+    state.extend(link);
+
+    alter_with(Address::new_id(target_actor_id), state);
+}
+
+// Read the ID of a fixture actor with the Fixture cheatcode.
+fn fixture(name: &str) -> u64 {
+    let res = fvm_sdk::send::send(
+        &Address::new_id(98),
+        method_hash!("Fixture"),
+        Some(IpldBlock::serialize(DAG_CBOR, &name).unwrap()),
+        TokenAmount::zero(),
+        None,
+        SendFlags::empty(),
+    )
+    .unwrap();
+
+    assert_eq!(res.exit_code, ExitCode::OK);
+    RawBytes::new(res.return_data.unwrap().data)
+        .deserialize()
+        .unwrap()
+}
+
+// Checks Fixture cheatcode happy path, a Token fixture actor being deployed with the target.
+#[allow(non_snake_case)]
+fn TestFixture(input: u32) {
+    let target_actor_id: u64 = deserialize_params(input);
+    let token_id = fixture("Token");
+
+    assert_ne!(token_id, target_actor_id);
+    assert!(fvm_sdk::actor::get_actor_code_cid(&Address::new_id(token_id)).is_some());
+}
+
+// Checks Fixture with a name that is not part of the fixture.
+#[allow(non_snake_case)]
+fn TestFailUnknownFixture(_input: u32) {
+    fixture("Unknown");
+}
+
+declare_tests_fail!(
+    "Warp",
+    "Epoch",
+    "Fee",
+    "ChainId",
+    "Prank",
+    "StartPrank",
+    "Trick",
+    "Alter",
+    "ExpectRevert",
+    "Deal",
+    "ExpectEmit",
+    "RevertTo",
+    "MockCall",
+    "ExpectCall",
+    "SetChainRandomness",
+    "SetBeaconRandomness",
+    "SetTipsetCid",
+    "MockConsensusFault",
+    "MockProofs",
+    "ExpectVerifyProof",
+    "NewAccount",
+    "AddrFromKey",
+    "Sign",
+    "MockSignatures",
+    "Deploy",
+    "GasLimit",
+    "Load",
+    "LoadBlock",
+    "Store",
+    "AlterWith"
+);
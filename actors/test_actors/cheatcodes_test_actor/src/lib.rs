@@ -1,5 +1,8 @@
 // Copyright 2023 Polyphene.
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+// The `match_method!` dispatching the test methods expands recursively for each of them.
+#![recursion_limit = "256"]
+
 #[cfg(target_arch = "wasm32")]
 mod actor;
@@ -0,0 +1,96 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use frc42_dispatch::match_method;
+use fvm_ipld_encoding::{de::DeserializeOwned, strict_bytes, RawBytes};
+use fvm_sdk as sdk;
+use fvm_sdk::NO_DATA_BLOCK_ID;
+use fvm_shared::error::ExitCode;
+use serde::Deserialize;
+
+/// Deserialize message parameters into given struct.
+pub fn deserialize_params<D: DeserializeOwned>(params: u32) -> D {
+    let params = fvm_sdk::message::params_raw(params)
+        .expect("Could not get message parameters")
+        .expect("Expected message parameters but got none");
+
+    let params = RawBytes::new(params.data);
+
+    params
+        .deserialize()
+        .expect("Should be able to deserialize message params into arguments of called method")
+}
+
+/// Parameters of `TestFuzzShortBytes`, the target actor ID followed by the generated bytes.
+#[derive(Deserialize)]
+struct ShortBytesParams(u64, #[serde(with = "strict_bytes")] Vec<u8>);
+
+#[no_mangle]
+fn invoke(input: u32) -> u32 {
+    std::panic::set_hook(Box::new(|info| {
+        sdk::vm::exit(
+            ExitCode::USR_ASSERTION_FAILED.value(),
+            None,
+            Some(&format!("{info}")),
+        )
+    }));
+
+    let method_num = sdk::message::method_number();
+    match_method!(
+        method_num,
+        {
+            "TestFuzzCommutative" => {
+                TestFuzzCommutative(input);
+                NO_DATA_BLOCK_ID
+            },
+            "TestFuzzBelowThreshold" => {
+                TestFuzzBelowThreshold(input);
+                NO_DATA_BLOCK_ID
+            },
+            "TestFuzzShortBytes" => {
+                TestFuzzShortBytes(input);
+                NO_DATA_BLOCK_ID
+            },
+            "TestFuzzOutOfGas" => {
+                TestFuzzOutOfGas(input);
+                NO_DATA_BLOCK_ID
+            },
+            _ => {
+                sdk::vm::abort(
+                    ExitCode::USR_UNHANDLED_MESSAGE.value(),
+                    Some("Unknown method number"),
+                );
+            }
+        }
+    )
+}
+
+// Fuzz test that should pass for every generated input.
+#[allow(non_snake_case)]
+fn TestFuzzCommutative(input: u32) {
+    let (_target_actor_id, a, b): (u64, u64, u64) = deserialize_params(input);
+    assert_eq!(a.wrapping_add(b), b.wrapping_add(a));
+}
+
+// Fuzz test that fails for any input greater or equal to 1000.
+#[allow(non_snake_case)]
+fn TestFuzzBelowThreshold(input: u32) {
+    let (_target_actor_id, value): (u64, u64) = deserialize_params(input);
+    assert!(value < 1000, "value {value} is not below 1000");
+}
+
+// Fuzz test that fails for any input of at least 4 bytes.
+#[allow(non_snake_case)]
+fn TestFuzzShortBytes(input: u32) {
+    let ShortBytesParams(_target_actor_id, bytes) = deserialize_params(input);
+    assert!(bytes.len() < 4, "bytes are too long");
+}
+
+// Fuzz test that runs out of gas for any input.
+#[allow(non_snake_case)]
+fn TestFuzzOutOfGas(input: u32) {
+    let (_target_actor_id, mut value): (u64, u64) = deserialize_params(input);
+    loop {
+        value = std::hint::black_box(value.wrapping_add(1));
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use cid::{multihash::Code, Cid};
+use frc42_dispatch::match_method;
+use fvm_ipld_blockstore::Block;
+use fvm_ipld_encoding::tuple::{Deserialize_tuple, Serialize_tuple};
+use fvm_ipld_encoding::DAG_CBOR;
+use fvm_ipld_encoding::{de::DeserializeOwned, RawBytes};
+use fvm_sdk as sdk;
+use fvm_sdk::NO_DATA_BLOCK_ID;
+use fvm_shared::error::ExitCode;
+use sdk::sys::ErrorNumber;
+use serde::ser;
+use thiserror::Error;
+
+/**************************************************
+ * Actor's state
+ **************************************************/
+
+#[derive(Serialize_tuple, Deserialize_tuple)]
+struct ActorState {
+    count: u64,
+}
+
+impl ActorState {
+    pub fn load(cid: &Cid) -> Self {
+        let data = sdk::ipld::get(cid).unwrap();
+        fvm_ipld_encoding::from_slice::<Self>(&data).unwrap()
+    }
+
+    pub fn save(&self) -> Cid {
+        let serialized = fvm_ipld_encoding::to_vec(self).unwrap();
+        let block = Block {
+            codec: DAG_CBOR,
+            data: serialized,
+        };
+        sdk::ipld::put(
+            Code::Blake2b256.into(),
+            32,
+            block.codec,
+            block.data.as_ref(),
+        )
+        .unwrap()
+    }
+}
+
+/**************************************************
+ * IPLD Utils
+ **************************************************/
+
+/// Deserialize message parameters into given struct.
+pub fn deserialize_params<D: DeserializeOwned>(params: u32) -> D {
+    let params = fvm_sdk::message::params_raw(params)
+        .expect("Could not get message parameters")
+        .expect("Expected message parameters but got none");
+
+    let params = RawBytes::new(params.data);
+
+    params
+        .deserialize()
+        .expect("Should be able to deserialize message params into arguments of called method")
+}
+
+#[derive(Error, Debug)]
+enum IpldError {
+    #[error("ipld encoding error: {0}")]
+    Encoding(#[from] fvm_ipld_encoding::Error),
+    #[error("ipld blockstore error: {0}")]
+    Blockstore(#[from] ErrorNumber),
+}
+
+fn return_ipld<T>(value: &T) -> std::result::Result<u32, IpldError>
+where
+    T: ser::Serialize + ?Sized,
+{
+    let bytes = fvm_ipld_encoding::to_vec(value)?;
+    Ok(sdk::ipld::put_block(DAG_CBOR, bytes.as_slice())?)
+}
+
+#[no_mangle]
+fn invoke(input: u32) -> u32 {
+    let method_num = fvm_sdk::message::method_number();
+    match_method!(
+        method_num,
+        {
+            "Constructor" => {
+                Constructor();
+                NO_DATA_BLOCK_ID
+            },
+            "Increment" => {
+                Increment();
+                NO_DATA_BLOCK_ID
+            },
+            "Add" => {
+                Add(input);
+                NO_DATA_BLOCK_ID
+            },
+            "Reset" => {
+                Reset();
+                NO_DATA_BLOCK_ID
+            },
+            "Count" => {
+                Count()
+            },
+            _ => {
+                fvm_sdk::vm::abort(
+                    ExitCode::USR_UNHANDLED_MESSAGE.value(),
+                    Some("Unknown method number"),
+                );
+            }
+        }
+    )
+}
+
+// `Constructor` for the target actor, initializes the counter.
+#[allow(non_snake_case)]
+fn Constructor() {
+    let state = ActorState { count: 0 };
+    let cid = state.save();
+    fvm_sdk::sself::set_root(&cid).unwrap();
+}
+
+#[allow(non_snake_case)]
+fn Increment() {
+    let mut state = ActorState::load(&sdk::sself::root().unwrap());
+    state.count = state.count.saturating_add(1);
+    let cid = state.save();
+    fvm_sdk::sself::set_root(&cid).unwrap();
+}
+
+#[allow(non_snake_case)]
+fn Add(input: u32) {
+    let (value,): (u64,) = deserialize_params(input);
+    let mut state = ActorState::load(&sdk::sself::root().unwrap());
+    state.count = state.count.saturating_add(value);
+    let cid = state.save();
+    fvm_sdk::sself::set_root(&cid).unwrap();
+}
+
+#[allow(non_snake_case)]
+fn Reset() {
+    let state = ActorState { count: 0 };
+    let cid = state.save();
+    fvm_sdk::sself::set_root(&cid).unwrap();
+}
+
+#[allow(non_snake_case)]
+fn Count() -> u32 {
+    let state = ActorState::load(&sdk::sself::root().unwrap());
+
+    return_ipld(&state.count).unwrap()
+}
@@ -0,0 +1,5 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+#[cfg(target_arch = "wasm32")]
+mod actor;
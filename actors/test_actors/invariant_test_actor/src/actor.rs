@@ -0,0 +1,95 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use frc42_dispatch::match_method;
+use frc42_dispatch::method_hash;
+use fvm_ipld_encoding::{de::DeserializeOwned, RawBytes};
+use fvm_sdk as sdk;
+use fvm_sdk::NO_DATA_BLOCK_ID;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sys::SendFlags;
+
+/// Deserialize message parameters into given struct.
+pub fn deserialize_params<D: DeserializeOwned>(params: u32) -> D {
+    let params = fvm_sdk::message::params_raw(params)
+        .expect("Could not get message parameters")
+        .expect("Expected message parameters but got none");
+
+    let params = RawBytes::new(params.data);
+
+    params
+        .deserialize()
+        .expect("Should be able to deserialize message params into arguments of called method")
+}
+
+/// Get the counter of the target actor.
+fn target_count(input: u32) -> u64 {
+    let target_actor_id: u64 = deserialize_params(input);
+
+    let res = fvm_sdk::send::send(
+        &Address::new_id(target_actor_id),
+        method_hash!("Count"),
+        None,
+        TokenAmount::zero(),
+        None,
+        SendFlags::READ_ONLY,
+    )
+    .unwrap();
+    assert_eq!(res.exit_code, ExitCode::OK);
+
+    RawBytes::new(
+        res.return_data
+            .expect("Should be able to get result from Count of target actor")
+            .data,
+    )
+    .deserialize()
+    .unwrap()
+}
+
+#[no_mangle]
+fn invoke(input: u32) -> u32 {
+    std::panic::set_hook(Box::new(|info| {
+        sdk::vm::exit(
+            ExitCode::USR_ASSERTION_FAILED.value(),
+            None,
+            Some(&format!("{info}")),
+        )
+    }));
+
+    let method_num = sdk::message::method_number();
+    match_method!(
+        method_num,
+        {
+            "InvariantCountBelowThreshold" => {
+                InvariantCountBelowThreshold(input);
+                NO_DATA_BLOCK_ID
+            },
+            "InvariantCountReadable" => {
+                InvariantCountReadable(input);
+                NO_DATA_BLOCK_ID
+            },
+            _ => {
+                sdk::vm::abort(
+                    ExitCode::USR_UNHANDLED_MESSAGE.value(),
+                    Some("Unknown method number"),
+                );
+            }
+        }
+    )
+}
+
+// Invariant that breaks as soon as the target counter reaches 1000.
+#[allow(non_snake_case)]
+fn InvariantCountBelowThreshold(input: u32) {
+    let count = target_count(input);
+    assert!(count < 1000, "count {count} is not below 1000");
+}
+
+// Invariant that holds whatever the calls made to the target actor.
+#[allow(non_snake_case)]
+fn InvariantCountReadable(input: u32) {
+    target_count(input);
+}
@@ -69,7 +69,7 @@ pub fn snapshot(args: &Args) -> Result<()> {
 
         let path = path
             .as_deref()
-            .unwrap_or_else(|| &Path::new(".gas-snapshot"));
+            .unwrap_or_else(|| Path::new(".gas-snapshot"));
 
         let equal = diff(&methods, path, check)?;
         if check && !equal {
@@ -114,8 +114,8 @@ fn diff(methods: &[MethodCost], path: &Path, check: bool) -> Result<bool> {
                     }
                     std::cmp::Ordering::Less => {
                         let mut more = (new - old) as f64 / old as f64 * 100.0;
-                        if more >= 1 as f64 {
-                            more = more.round() as f64;
+                        if more >= 1.0 {
+                            more = more.round();
                         }
                         log::info!(
                             "{}: gas used is {}% more ( {new} > {old} )",
@@ -125,8 +125,8 @@ fn diff(methods: &[MethodCost], path: &Path, check: bool) -> Result<bool> {
                     }
                     std::cmp::Ordering::Greater => {
                         let mut less = (old - new) as f64 / old as f64 * 100.0;
-                        if less >= 1 as f64 {
-                            less = less.round() as f64;
+                        if less >= 1.0 {
+                            less = less.round();
                         }
 
                         log::info!(
@@ -172,11 +172,13 @@ fn generate(path: &Path) -> Result<Vec<MethodCost>> {
                         passed = true;
                         ret
                     }
-                    kythera_lib::TestResultType::Failed(ret) => {
+                    kythera_lib::TestResultType::Failed(ret)
+                    | kythera_lib::TestResultType::OutOfGas(ret) => {
                         passed = false;
                         ret
                     }
-                    kythera_lib::TestResultType::Erred(_) => continue,
+                    kythera_lib::TestResultType::ExpectationFailed(_)
+                    | kythera_lib::TestResultType::Erred(_) => continue,
                 };
                 let name = format!("{}::{}", actor_name, result.method().name());
                 let cost = ret.msg_receipt.gas_used;
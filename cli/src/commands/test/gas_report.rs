@@ -34,10 +34,7 @@ impl GasReport {
             Some((actor, info)) => (actor, info),
             None => (actor.clone(), ActorInfo::default()),
         };
-        let mut gas_info = match info.methods.remove(&method) {
-            Some(gi) => gi,
-            None => vec![],
-        };
+        let mut gas_info = info.methods.remove(&method).unwrap_or_default();
         gas_info.push(cost);
         info.methods.insert(method.clone(), gas_info);
         self.reports.insert(actor, info);
@@ -57,8 +54,10 @@ impl GasReport {
 
         for result in test_results {
             let apply_ret = match result.ret() {
-                TestResultType::Passed(apply_ret) | TestResultType::Failed(apply_ret) => apply_ret,
-                TestResultType::Erred(_) => {
+                TestResultType::Passed(apply_ret)
+                | TestResultType::Failed(apply_ret)
+                | TestResultType::OutOfGas(apply_ret) => apply_ret,
+                TestResultType::ExpectationFailed(_) | TestResultType::Erred(_) => {
                     continue;
                 }
             };
@@ -98,18 +97,13 @@ impl GasReport {
 
                         // If the method called was from the target actor
                         // we create a new call on `GasInfo` with the totals of gas charge.
-                        let Some(method) = actor
-                                .abi()
-                                .methods()
-                                .iter()
-                                .find(|a| a.number() == method_return.num && method_return.at == *actor_id) else {
+                        let Some(method) = actor.abi().methods().iter().find(|a| {
+                            a.number() == method_return.num && method_return.at == *actor_id
+                        }) else {
                             continue;
                         };
 
-                        let mut gas_info = match info.methods.remove(method) {
-                            Some(gi) => gi,
-                            None => vec![],
-                        };
+                        let mut gas_info = info.methods.remove(method).unwrap_or_default();
                         gas_info.push(method_return.gas_cost);
                         info.methods.insert(method.clone(), gas_info);
                     }
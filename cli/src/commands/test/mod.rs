@@ -1,17 +1,22 @@
 mod gas_report;
 
 use crate::commands::ARTIFACTS_DIR;
-use crate::utils::search::search_files;
+use crate::utils::context::{CliContext, ConstructorConfig, FixtureActorConfig, GasSettings};
+use crate::utils::search::{read_actor, search_files, Test};
+use anyhow::Context;
 use clap::builder::ValueHint;
 use clap::ArgAction;
 use colored::Colorize;
 use kythera_lib::{
-    ApplyRet, ExecutionEvent, MethodType, TestResult, TestResultType, Tester, WasmActor,
+    format_event, ActorID, Address, ApplyRet, ExecutionEvent, FixtureActor, FuzzConfig, GasConfig,
+    InvariantConfig, MethodType, RawBytes, StampedEvent, TestEvent, TestResult, TestResultType,
+    Tester, TokenAmount, WasmActor,
 };
+use std::collections::HashMap;
 use std::error::Error;
 use std::{
     path::PathBuf,
-    sync::mpsc::{sync_channel, Receiver, SyncSender},
+    sync::mpsc::{channel, sync_channel, Receiver, SyncSender},
     thread,
 };
 
@@ -42,16 +47,160 @@ pub struct Args {
     /// Print gas reports.
     #[clap(long)]
     gas_report: bool,
+
+    /// Number of runs with generated inputs for each fuzz test.
+    #[clap(long, value_name = "RUNS", default_value_t = FuzzConfig::default().runs)]
+    fuzz_runs: u32,
+
+    /// Seed used to generate the fuzz tests inputs and the invariant tests call sequences.
+    #[clap(long, value_name = "SEED", default_value_t = FuzzConfig::default().seed)]
+    fuzz_seed: u64,
+
+    /// Number of call sequences run against the target actor for invariant tests.
+    #[clap(long, value_name = "RUNS", default_value_t = InvariantConfig::default().runs)]
+    invariant_runs: u32,
+
+    /// Number of calls to the target actor in each invariant tests call sequence.
+    #[clap(long, value_name = "DEPTH", default_value_t = InvariantConfig::default().depth)]
+    invariant_depth: u32,
+
+    /// Number of threads running the tests.
+    #[clap(long, short, value_name = "JOBS", default_value_t = 1)]
+    jobs: usize,
+
+    /// Gas limit of each test method and constructor, overriding the configuration.
+    #[clap(long, value_name = "GAS")]
+    gas_limit: Option<u64>,
+
+    /// Gas fee cap of the messages in attoFIL, overriding the configuration.
+    #[clap(long, value_name = "ATTOFIL")]
+    gas_fee_cap: Option<u128>,
+
+    /// Gas premium of the messages in attoFIL, overriding the configuration.
+    #[clap(long, value_name = "ATTOFIL")]
+    gas_premium: Option<u128>,
+
+    /// Base fee of the network in attoFIL, overriding the configuration.
+    #[clap(long, value_name = "ATTOFIL")]
+    base_fee: Option<u128>,
+}
+
+/// Build the [`GasConfig`] from the command line arguments, falling back to the configuration
+/// and then to the default values.
+fn gas_config(args: &Args, gas: &GasSettings) -> GasConfig {
+    let default = GasConfig::default();
+    GasConfig {
+        gas_limit: args.gas_limit.or(gas.gas_limit),
+        gas_fee_cap: args
+            .gas_fee_cap
+            .or(gas.gas_fee_cap)
+            .map_or(default.gas_fee_cap, TokenAmount::from_atto),
+        gas_premium: args
+            .gas_premium
+            .or(gas.gas_premium)
+            .map_or(default.gas_premium, TokenAmount::from_atto),
+        base_fee: args
+            .base_fee
+            .or(gas.base_fee)
+            .map_or(default.base_fee, TokenAmount::from_atto),
+    }
+}
+
+/// Set the constructor parameters declared in the configuration of an actor.
+fn with_constructor_params(
+    actor: WasmActor,
+    constructor: &ConstructorConfig,
+) -> anyhow::Result<WasmActor> {
+    let params = hex::decode(&constructor.params)
+        .with_context(|| format!("Invalid constructor parameters for actor {}", actor.name()))?;
+
+    Ok(actor.with_constructor_params(
+        RawBytes::new(params),
+        TokenAmount::from_atto(constructor.value),
+    ))
+}
+
+/// Set the constructor parameters declared in the configuration of a target actor and of its
+/// test actors.
+fn with_constructors(
+    mut test_target: Test,
+    constructors: &HashMap<String, ConstructorConfig>,
+) -> anyhow::Result<Test> {
+    for actor in std::iter::once(&mut test_target.actor).chain(&mut test_target.tests) {
+        if let Some(constructor) = constructors.get(actor.name()) {
+            *actor = with_constructor_params(actor.clone(), constructor)?;
+        }
+    }
+    Ok(test_target)
+}
+
+/// Read the fixture actors declared in the configuration.
+fn read_fixture(fixture: &[FixtureActorConfig]) -> anyhow::Result<Vec<FixtureActor>> {
+    fixture
+        .iter()
+        .map(|config| {
+            Ok(FixtureActor {
+                name: config.name.clone(),
+                actor: read_actor(&config.binary)?,
+                constructor_params: RawBytes::new(
+                    hex::decode(&config.constructor_params).with_context(|| {
+                        format!(
+                            "Invalid constructor parameters for fixture actor {}",
+                            config.name
+                        )
+                    })?,
+                ),
+                balance: TokenAmount::from_atto(config.balance),
+                id: config.id,
+            })
+        })
+        .collect()
 }
 
 /// Kythera cli test command.
 pub fn test(args: &Args) -> anyhow::Result<()> {
+    let context = CliContext::new()?;
     let test_targets = search_files(&args.path)?;
     let mut gas_report = GasReport::default();
     let mut tester = Tester::new();
+    tester.set_fuzz_config(FuzzConfig {
+        runs: args.fuzz_runs,
+        seed: args.fuzz_seed,
+    });
+    tester.set_invariant_config(InvariantConfig {
+        runs: args.invariant_runs,
+        depth: args.invariant_depth,
+    });
+    tester.set_gas_config(gas_config(args, &context.gas));
+    tester.set_jobs(args.jobs);
+
+    // Set the constructor parameters declared for the target and test actors, the invalid ones
+    // being reported along with the tests of their target actor.
+    let test_targets = test_targets
+        .into_iter()
+        .map(|test_target| with_constructors(test_target, &context.constructors))
+        .collect::<Vec<anyhow::Result<Test>>>();
+
+    // Register every target and test actor so that the tests can deploy them.
+    for actor in test_targets
+        .iter()
+        .flatten()
+        .flat_map(|test_target| std::iter::once(&test_target.actor).chain(&test_target.tests))
+    {
+        if let Err(err) = tester.register_actor(actor) {
+            log::warn!("Could not register Actor {}: {}", actor.name(), err);
+        }
+    }
 
     // Iterate through target actors and respective tests.
     for test_target in test_targets {
+        let test_target = match test_target {
+            Ok(test_target) => test_target,
+            Err(err) => {
+                log::error!("\nError: {:#}", err);
+                continue;
+            }
+        };
         log::info!("\tRunning Tests for Actor : {}", test_target.actor.name());
         let constructor = test_target.actor.abi().constructor().cloned();
 
@@ -79,49 +228,76 @@ pub fn test(args: &Args) -> anyhow::Result<()> {
             _ => {}
         }
 
-        // Filter the [`Method`]s to be test, `MethodType::Test` `MethodType::TestFail`.
+        // Deploy the fixture actors declared for the target actor.
+        if let Some(fixture) = context.fixtures.get(test_target.actor.name()) {
+            let fixture = match read_fixture(fixture) {
+                Ok(fixture) => fixture,
+                Err(err) => {
+                    log::error!("\nError: {:#}", err);
+                    continue;
+                }
+            };
+            if let Err(err) = tester.deploy_fixture(&fixture) {
+                log::error!("\nError: {}", err);
+                if let Some(source) = err.source() {
+                    log::error!("Caused by: {}", source)
+                }
+                continue;
+            }
+        }
+
+        // Filter the [`Method`]s to be test, `MethodType::Test` `MethodType::TestFail`
+        // `MethodType::TestFuzz` `MethodType::Invariant`.
         let populated_tests = test_target
             .tests
             .iter()
             .filter(|test| {
                 test.abi().methods().iter().any(|method| {
-                    matches!(method.r#type(), MethodType::Test | MethodType::TestFail)
+                    matches!(
+                        method.r#type(),
+                        MethodType::Test
+                            | MethodType::TestFail
+                            | MethodType::TestFuzz
+                            | MethodType::Invariant
+                    )
                 })
             })
             .collect::<Vec<&WasmActor>>();
 
         log::info!("\t\tTesting {} test files\n", populated_tests.len());
 
-        // Iterate through test actors.
-        for test in populated_tests {
-            // Create two channels, one for streaming the result,
-            // and another for synchronization when the streaming is over.
-            let (sync_tx, sync_rx) = sync_channel(1);
-            let (stream_tx, stream_rx) = sync_channel(10);
+        // Create two channels, one for streaming the result,
+        // and another for synchronization when the streaming is over.
+        let (sync_tx, sync_rx) = sync_channel(1);
+        let (stream_tx, stream_rx) = channel();
+
+        let verbosity = args.verbosity;
+        thread::spawn(move || stream_results(stream_rx, sync_tx, verbosity));
 
-            let verbosity = args.verbosity;
-            thread::spawn(move || stream_results(stream_rx, sync_tx, verbosity));
+        let results = tester.test_all(&populated_tests, Some(stream_tx));
 
-            match tester.test(test, Some(stream_tx)) {
-                Ok(results) => {
-                    if args.gas_report {
+        sync_rx
+            .recv()
+            .expect("Should be able to sync the end of streaming results");
+
+        // The setup errors of the test actors have been streamed with their results.
+        match results {
+            Ok(results) => {
+                for test_actor_results in results {
+                    if let (Ok(results), true) = (test_actor_results.results, args.gas_report) {
                         let deployed = tester
                             .deployed_actor()
                             .expect("Deployed actor should be available");
                         gas_report.analyze_results(deployed, &results);
                     }
                 }
-                Err(err) => {
-                    log::error!("\nError: {}", err);
-                    if let Some(source) = err.source() {
-                        log::error!("Caused by: {}", source)
-                    }
+            }
+            Err(err) => {
+                log::error!("\nError: {}", err);
+                if let Some(source) = err.source() {
+                    log::error!("Caused by: {}", source)
                 }
-            };
-
-            sync_rx
-                .recv()
-                .expect("Should be able to sync the end of streaming results");
+            }
         }
     }
 
@@ -135,42 +311,78 @@ pub fn test(args: &Args) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Stream the results received from `Tester::test, so that users see the result of each test as
-/// soon as it finishes.
-fn stream_results(
-    stream: Receiver<(WasmActor, TestResult)>,
-    sync_tx: SyncSender<()>,
-    verbosity: u8,
-) {
+/// Stream the progress received from `Tester::test_all`, so that users see the result of each
+/// test as soon as it finishes.
+fn stream_results(stream: Receiver<TestEvent>, sync_tx: SyncSender<()>, verbosity: u8) {
+    let mut started = false;
     let mut tests_failed = vec![];
     let mut tests_passed = vec![];
-    // Default failed will be shown for test actors that returned errors on setup.
-    let mut result = "FAILED".bright_red();
-    for (_actor, test_result) in stream {
+    for event in stream {
+        let test_result = match event {
+            TestEvent::Started { name, tests } => {
+                if started {
+                    print_summary(&tests_passed, &tests_failed);
+                    tests_passed.clear();
+                    tests_failed.clear();
+                }
+                started = true;
+                log::info!("{name}: testing {tests} tests");
+                continue;
+            }
+            TestEvent::SetupFailed(err) => {
+                log::error!("\nError: {err}");
+                continue;
+            }
+            TestEvent::Result(test_result) => *test_result,
+        };
+
         log::info!("{test_result}");
         match test_result.ret() {
-            TestResultType::Passed(apply_ret) | TestResultType::Failed(apply_ret) => {
+            TestResultType::Passed(apply_ret)
+            | TestResultType::Failed(apply_ret)
+            | TestResultType::OutOfGas(apply_ret) => {
                 log::info!("(gas consumption: {})", apply_ret.msg_receipt.gas_used);
+                if let Some(report) = test_result.fuzz_report() {
+                    log::info!("(runs: {})", report.runs());
+                }
+                if let Some(report) = test_result.invariant_report() {
+                    log::info!("(runs: {}, calls: {})", report.runs(), report.calls());
+                }
                 // 'vvv', prints all traces.
                 if verbosity == 3 {
-                    print_verbose_traces(apply_ret);
+                    print_verbose_traces(apply_ret, test_result.labels());
+                    print_events(test_result.events(), test_result.labels());
                 }
                 if test_result.passed() {
                     tests_passed.push(test_result);
                 } else {
                     // 'vv', prints failing traces.
                     if verbosity == 2 {
-                        print_verbose_traces(apply_ret);
+                        print_verbose_traces(apply_ret, test_result.labels());
+                        print_events(test_result.events(), test_result.labels());
                     }
                     tests_failed.push(test_result);
                 }
             }
-            TestResultType::Erred(_) => {
+            TestResultType::ExpectationFailed(_) | TestResultType::Erred(_) => {
                 tests_failed.push(test_result);
             }
         }
     }
 
+    if started {
+        print_summary(&tests_passed, &tests_failed);
+    }
+    sync_tx
+        .send(())
+        .expect("Should be able to sync finish streaming results");
+}
+
+/// Print the failures and the sum of failed and passed tests of a test actor.
+fn print_summary(tests_passed: &[TestResult], tests_failed: &[TestResult]) {
+    // Default failed will be shown for test actors that returned errors on setup.
+    let mut result = "FAILED".bright_red();
+
     // Optimist mindset that if we got returned values and some of them are passing then all
     // are passing.
     if !tests_passed.is_empty() {
@@ -188,7 +400,19 @@ fn stream_results(
                 (_, TestResultType::Erred(err)) => {
                     log::error!("\nError: {err}");
                 }
-                (MethodType::Test, TestResultType::Failed(apply_ret)) => {
+                (_, TestResultType::ExpectationFailed(failure)) => {
+                    log::error!("failed: {failure}");
+                }
+                (_, TestResultType::OutOfGas(apply_ret)) => {
+                    log::error!(
+                        "failed: out of gas after using {} gas",
+                        apply_ret.msg_receipt.gas_used
+                    );
+                }
+                (
+                    MethodType::Test | MethodType::TestFuzz | MethodType::Invariant,
+                    TestResultType::Failed(apply_ret),
+                ) => {
                     let info = apply_ret
                         .failure_info
                         .as_ref()
@@ -199,7 +423,23 @@ fn stream_results(
                     log::error!("failed: test exited with exit code 0");
                 }
                 (_, TestResultType::Passed(_)) => panic!("Test should have failed"),
-                _ => panic!("Failed tests should be of type test or test fail"),
+                _ => {
+                    panic!("Failed tests should be of type test, test fail, test fuzz or invariant")
+                }
+            }
+            if let Some(counterexample) = f.fuzz_report().and_then(|r| r.counterexample()) {
+                let inputs = counterexample
+                    .iter()
+                    .map(|input| input.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                log::error!("counterexample: ({inputs})");
+            }
+            if let Some(sequence) = f.invariant_report().and_then(|r| r.counterexample()) {
+                log::error!("call sequence:");
+                for (i, call) in sequence.iter().enumerate() {
+                    log::error!("  {}. {call}", i + 1);
+                }
             }
         }
     }
@@ -209,13 +449,20 @@ fn stream_results(
         tests_passed.len(),
         tests_failed.len()
     );
-    sync_tx
-        .send(())
-        .expect("Should be able to sync finish streaming results");
+}
+
+/// Name an actor by its label if it has one, or by its address.
+fn actor_name(address: &Address, labels: &HashMap<ActorID, String>) -> String {
+    address
+        .id()
+        .ok()
+        .and_then(|id| labels.get(&id))
+        .cloned()
+        .unwrap_or_else(|| address.to_string())
 }
 
 /// Print the traces and gas consumptions of each test.
-fn print_verbose_traces(apply_ret: &ApplyRet) {
+fn print_verbose_traces(apply_ret: &ApplyRet, labels: &HashMap<ActorID, String>) {
     for trace in apply_ret.exec_trace.iter() {
         match trace {
             // OnChainReturnValue doesn't have costs.
@@ -227,6 +474,11 @@ fn print_verbose_traces(apply_ret: &ApplyRet) {
             kythera_lib::ExecutionEvent::Call {
                 from, to, method, ..
             } => {
+                let from = labels
+                    .get(from)
+                    .cloned()
+                    .unwrap_or_else(|| from.to_string());
+                let to = actor_name(to, labels);
                 log::info!("├─ [<Call>] from {from} to {to} method: {method}");
             }
             ExecutionEvent::CallReturn(exit_code, _) => {
@@ -240,3 +492,14 @@ fn print_verbose_traces(apply_ret: &ApplyRet) {
         }
     }
 }
+
+/// Print the events emitted during a test.
+fn print_events(events: &[StampedEvent], labels: &HashMap<ActorID, String>) {
+    for event in events {
+        log::info!(
+            "├─ [<Event>] emitted by {}: {}",
+            actor_name(&Address::new_id(event.emitter), labels),
+            format_event(&event.event)
+        );
+    }
+}
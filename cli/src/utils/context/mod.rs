@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::path::PathBuf;
@@ -19,6 +20,55 @@ enum Error {
     InvalidConfFile,
     #[error("error with actors_bin_dir")]
     FailedToGetActorsBinDirAsStr,
+    #[error("error with fixture actor binary")]
+    FailedToGetFixtureBinaryAsStr,
+}
+
+/// Parameters and value sent to the constructor of an actor.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct ConstructorConfig {
+    /// Hex encoded parameters of the constructor.
+    #[serde(default)]
+    pub params: String,
+    /// Value sent by the testing account, in attoFIL.
+    #[serde(default)]
+    pub value: u128,
+}
+
+/// Gas settings of the messages running the constructors and the test methods, the default ones
+/// being used for the missing settings.
+#[derive(PartialEq, Debug, Default, Serialize, Deserialize, Clone)]
+pub struct GasSettings {
+    /// Gas limit of each message.
+    #[serde(default)]
+    pub gas_limit: Option<u64>,
+    /// Gas fee cap of the messages, in attoFIL.
+    #[serde(default)]
+    pub gas_fee_cap: Option<u128>,
+    /// Gas premium of the messages, in attoFIL.
+    #[serde(default)]
+    pub gas_premium: Option<u128>,
+    /// Base fee of the network, in attoFIL.
+    #[serde(default)]
+    pub base_fee: Option<u128>,
+}
+
+/// Actor deployed along with a target actor, whose ID is read by name by its test methods.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct FixtureActorConfig {
+    /// Name under which the actor ID is read by the test methods.
+    pub name: String,
+    /// Path to the actor binary, its ABI being read next to it.
+    pub binary: PathBuf,
+    /// Hex encoded parameters of the actor constructor.
+    #[serde(default)]
+    pub constructor_params: String,
+    /// Balance the actor is provided with, in attoFIL.
+    #[serde(default)]
+    pub balance: u128,
+    /// ID the actor is deployed at, the next available one if none.
+    #[serde(default)]
+    pub id: Option<u64>,
 }
 
 /// Context structure helping accessing the repository area in a consistent way throughout the CLI
@@ -27,6 +77,12 @@ enum Error {
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct CliContext {
     pub actors_bin_dir: PathBuf,
+    /// Constructor parameters of the target and test actors, by actor file name.
+    pub constructors: HashMap<String, ConstructorConfig>,
+    /// Fixture actors of the target actors, by target actor file name.
+    pub fixtures: HashMap<String, Vec<FixtureActorConfig>>,
+    /// Gas settings of the executed messages.
+    pub gas: GasSettings,
 }
 
 impl CliContext {
@@ -46,6 +102,9 @@ impl CliContext {
             actors_bin_dir: config
                 .actors_bin_dir
                 .unwrap_or_else(|| root_path.join("artifacts")),
+            constructors: config.constructors.unwrap_or_default(),
+            fixtures: config.fixtures.unwrap_or_default(),
+            gas: config.gas.unwrap_or_default(),
         };
         // secure context by checking that targeted paths are part of the project
         let actors_bin_dir_str = context
@@ -54,6 +113,87 @@ impl CliContext {
             .context(Error::FailedToGetActorsBinDirAsStr)
             .unwrap();
         to_relative_path_to_project_root(actors_bin_dir_str)?;
+        for fixture_actor in context.fixtures.values().flatten() {
+            let binary_str = fixture_actor
+                .binary
+                .to_str()
+                .context(Error::FailedToGetFixtureBinaryAsStr)?;
+            to_relative_path_to_project_root(binary_str)?;
+        }
         Ok(context)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixtures() {
+        let config: OptionalCliContext = serde_yaml::from_str(
+            r#"
+fixtures:
+  Market.wasm:
+    - name: Token
+      binary: artifacts/Token.wasm
+      constructor_params: "8201"
+      balance: 100000000000000000000
+      id: 1000
+    - name: Oracle
+      binary: artifacts/Oracle.wasm
+"#,
+        )
+        .unwrap();
+
+        let fixtures = config.fixtures.unwrap();
+        let market = &fixtures["Market.wasm"];
+        assert_eq!(market[0].name, "Token");
+        assert_eq!(market[0].constructor_params, "8201");
+        assert_eq!(market[0].balance, 100_000_000_000_000_000_000);
+        assert_eq!(market[0].id, Some(1000));
+        assert_eq!(market[1].binary, PathBuf::from("artifacts/Oracle.wasm"));
+        assert_eq!(market[1].balance, 0);
+        assert_eq!(market[1].id, None);
+        assert!(config.actors_bin_dir.is_none());
+    }
+
+    #[test]
+    fn test_parse_constructors() {
+        let config: OptionalCliContext = serde_yaml::from_str(
+            r#"
+constructors:
+  Market.wasm:
+    params: "8201"
+    value: 1000
+  Market.t.wasm:
+    value: 10
+"#,
+        )
+        .unwrap();
+
+        let constructors = config.constructors.unwrap();
+        assert_eq!(constructors["Market.wasm"].params, "8201");
+        assert_eq!(constructors["Market.wasm"].value, 1000);
+        assert_eq!(constructors["Market.t.wasm"].params, "");
+        assert_eq!(constructors["Market.t.wasm"].value, 10);
+        assert!(config.fixtures.is_none());
+    }
+
+    #[test]
+    fn test_parse_gas() {
+        let config: OptionalCliContext = serde_yaml::from_str(
+            r#"
+gas:
+  gas_limit: 10000000
+  gas_fee_cap: 200
+"#,
+        )
+        .unwrap();
+
+        let gas = config.gas.unwrap();
+        assert_eq!(gas.gas_limit, Some(10_000_000));
+        assert_eq!(gas.gas_fee_cap, Some(200));
+        assert_eq!(gas.gas_premium, None);
+        assert_eq!(gas.base_fee, None);
+    }
+}
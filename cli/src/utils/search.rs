@@ -53,7 +53,7 @@ fn set_abi_extension<P: AsRef<Path>>(path: P) -> anyhow::Result<String> {
 }
 
 /// Read a WebAssembly actor from a binary and an Abi.
-fn read_actor<P: AsRef<Path>>(binary_path: P) -> anyhow::Result<WasmActor> {
+pub(crate) fn read_actor<P: AsRef<Path>>(binary_path: P) -> anyhow::Result<WasmActor> {
     let abi_path = set_abi_extension(&binary_path)?;
     let (file_name, bytecode) = read_file_data(binary_path)?;
 
@@ -85,7 +85,7 @@ fn outputs_single_passed_tests() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert()
         .success()
         .stdout(contains("\tRunning Tests for Actor : Target.wasm"))
@@ -138,7 +138,7 @@ fn outputs_single_tests_failed() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert()
         .success()
         .stdout(contains("\tRunning Tests for Actor : Target.wasm"))
@@ -207,7 +207,7 @@ fn outputs_single_error_target_file() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert().success()
         .stdout(contains("\tRunning Tests for Actor : Target.wasm"))
         .stdout(contains(
@@ -256,7 +256,7 @@ fn outputs_single_error_test_file() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert().success()
         .stdout(contains("\tRunning Tests for Actor : Target.wasm"))
         .stdout(contains("\t\tTesting 1 test files"))
@@ -334,7 +334,7 @@ fn outputs_multiple_passed_tests() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert()
         .success()
         .stdout(contains("\tRunning Tests for Actor : FirstTarget.wasm"))
@@ -422,7 +422,7 @@ fn outputs_multiple_failed_tests() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert()
         .success()
         .stdout(contains("\tRunning Tests for Actor : FirstTarget.wasm"))
@@ -523,7 +523,7 @@ fn outputs_gas_report() {
     cmd.args([
         "test",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--gas-report",
     ])
     .assert()
@@ -589,7 +589,7 @@ fn creates_gas_snapshot() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--snap",
         path.to_str().unwrap(),
     ])
@@ -600,7 +600,6 @@ fn creates_gas_snapshot() {
     let mut rdr = csv::Reader::from_reader(file);
     let snapshot = rdr
         .deserialize::<MethodCost>()
-        .into_iter()
         .filter_map(|r| r.ok())
         .collect::<Vec<_>>();
     assert_eq!(snapshot.len(), 1);
@@ -647,7 +646,7 @@ fn snapshot_check_success() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--snap",
         path.to_str().unwrap(),
     ])
@@ -658,7 +657,7 @@ fn snapshot_check_success() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--check",
         path.to_str().unwrap(),
     ])
@@ -705,7 +704,7 @@ fn snapshot_check_fails_differences() {
     cmd.args([
         "snapshot",
         "--path", 
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--check",
         path2.to_str().unwrap(),
     ])
@@ -754,7 +753,7 @@ fn snapshpt_prints_diff_same_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--snap",
         path.to_str().unwrap(),
     ])
@@ -765,7 +764,7 @@ fn snapshpt_prints_diff_same_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--diff",
         path.to_str().unwrap(),
     ])
@@ -815,7 +814,7 @@ fn snapshot_prints_diff_more_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--snap",
         path.to_str().unwrap(),
     ])
@@ -826,7 +825,6 @@ fn snapshot_prints_diff_more_gas_usage() {
     let mut rdr = csv::Reader::from_reader(file);
     let mut snapshot = rdr
         .deserialize::<MethodCost>()
-        .into_iter()
         .filter_map(|r| r.ok())
         .collect::<Vec<_>>();
 
@@ -845,7 +843,7 @@ fn snapshot_prints_diff_more_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--diff",
         path2.to_str().unwrap(),
     ])
@@ -896,7 +894,7 @@ fn snapshot_prints_diff_less_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--snap",
         path.to_str().unwrap(),
     ])
@@ -907,7 +905,6 @@ fn snapshot_prints_diff_less_gas_usage() {
     let mut rdr = csv::Reader::from_reader(file);
     let mut snapshot = rdr
         .deserialize::<MethodCost>()
-        .into_iter()
         .filter_map(|r| r.ok())
         .collect::<Vec<_>>();
 
@@ -928,7 +925,7 @@ fn snapshot_prints_diff_less_gas_usage() {
     cmd.args([
         "snapshot",
         "--path",
-        &dir.path().to_str().unwrap(),
+        dir.path().to_str().unwrap(),
         "--diff",
         path2.to_str().unwrap(),
     ])
@@ -974,7 +971,7 @@ fn outputs_log_cheatcode() {
     );
 
     let mut cmd = Command::cargo_bin("kythera").unwrap();
-    cmd.args(["test", "--path", &dir.path().to_str().unwrap()])
+    cmd.args(["test", "--path", dir.path().to_str().unwrap()])
         .assert()
         .success()
         .stdout(contains("hello from actor"));
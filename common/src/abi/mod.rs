@@ -3,6 +3,7 @@
 
 use std::borrow::Borrow;
 use std::fmt;
+use std::str::FromStr;
 
 use anyhow::Result;
 use frc42_dispatch::hash::MethodResolver;
@@ -74,13 +75,12 @@ impl serde::Serialize for Abi {
     {
         let mut s = vec![];
         if let Some(constructor) = &self.constructor {
-            s.push(vec![constructor.name()]);
+            s.push(constructor);
         }
-        if let Some(constructor) = &self.set_up {
-            s.push(vec![constructor.name()]);
+        if let Some(set_up) = &self.set_up {
+            s.push(set_up);
         }
-        let methods = self.methods.iter().map(|m| vec![m.name()]);
-        s.extend(methods);
+        s.extend(self.methods.iter());
 
         serde::Serialize::serialize(&vec![s], serializer)
     }
@@ -149,6 +149,8 @@ pub struct Method {
     number: MethodNum,
     name: String,
     r#type: MethodType,
+    params: Vec<ParamType>,
+    value: u128,
 }
 
 impl fmt::Display for Method {
@@ -171,6 +173,75 @@ pub enum MethodType {
     Setup,
     Test,
     TestFail,
+    TestFuzz,
+    Invariant,
+}
+
+/// Type of a parameter expected by a [`Method`], used to generate inputs for
+/// [`MethodType::TestFuzz`] methods.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParamType {
+    Bool,
+    U64,
+    I64,
+    Bytes,
+    String,
+    Address,
+    Array(Box<ParamType>),
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::U64 => write!(f, "u64"),
+            ParamType::I64 => write!(f, "i64"),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Address => write!(f, "address"),
+            ParamType::Array(inner) => write!(f, "{inner}[]"),
+        }
+    }
+}
+
+impl FromStr for ParamType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(inner) = s.strip_suffix("[]") {
+            return Ok(ParamType::Array(Box::new(inner.parse()?)));
+        }
+
+        match s {
+            "bool" => Ok(ParamType::Bool),
+            "u64" => Ok(ParamType::U64),
+            "i64" => Ok(ParamType::I64),
+            "bytes" => Ok(ParamType::Bytes),
+            "string" => Ok(ParamType::String),
+            "address" => Ok(ParamType::Address),
+            _ => Err(Error::InvalidParamType(s.into())),
+        }
+    }
+}
+
+/// [`ParamType`]s are serialized as their string representation, i.e. `u64` or `bytes[]`.
+impl serde::Serialize for ParamType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ParamType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 impl Method {
@@ -188,6 +259,28 @@ impl Method {
     pub fn r#type(&self) -> MethodType {
         self.r#type
     }
+
+    /// Get the [`Method`] parameters schema.
+    pub fn params(&self) -> &[ParamType] {
+        &self.params
+    }
+
+    /// Set the [`Method`] parameters schema.
+    pub fn with_params(mut self, params: Vec<ParamType>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Get the value in attoFIL sent with the messages calling the [`Method`].
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    /// Set the value in attoFIL sent with the messages calling the [`Method`].
+    pub fn with_value(mut self, value: u128) -> Self {
+        self.value = value;
+        self
+    }
 }
 
 impl Method {
@@ -200,7 +293,9 @@ impl Method {
             ["Constructor", ..] => MethodType::Constructor,
             ["Setup", ..] => MethodType::Setup,
             ["Test", "Fail", ..] => MethodType::TestFail,
+            ["Test", "Fuzz", ..] => MethodType::TestFuzz,
             ["Test", ..] => MethodType::Test,
+            ["Invariant", ..] => MethodType::Invariant,
             _ => MethodType::Entrypoint,
         };
 
@@ -208,12 +303,41 @@ impl Method {
             number,
             name,
             r#type,
+            params: vec![],
+            value: 0,
         })
     }
 }
 
+/// Implement custom serialization method for [`Method`] so that only the `name` and, if any,
+/// the `params` schema and the `value` are serialized.
+impl serde::Serialize for Method {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let len = match (self.params.is_empty(), self.value == 0) {
+            (true, true) => 1,
+            (false, true) => 2,
+            (_, false) => 3,
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        seq.serialize_element(&self.name)?;
+        if len > 1 {
+            seq.serialize_element(&self.params)?;
+        }
+        if len > 2 {
+            seq.serialize_element(&self.value)?;
+        }
+        seq.end()
+    }
+}
+
 /// Implement custom deserialization method for [`Method`] as we expect the bytes to be deserialized to only contain
-/// the `name` and not the `number` property that is generated at deserialization time.
+/// the `name` and optionally the `params` schema and the `value`, and not the `number` property that is generated at
+/// deserialization time.
 impl<'de> serde::de::Deserialize<'de> for Method {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -236,9 +360,14 @@ impl<'de> serde::de::Deserialize<'de> for Method {
                     .next_element::<String>()?
                     .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
 
-                Self::Value::new_from_name(&name).map_err(|_| {
-                    serde::de::Error::custom(format!("Couldn't deserialize method: {}", &name))
-                })
+                let params = seq.next_element::<Vec<ParamType>>()?.unwrap_or_default();
+                let value = seq.next_element::<u128>()?.unwrap_or_default();
+
+                Self::Value::new_from_name(&name)
+                    .map(|method| method.with_params(params).with_value(value))
+                    .map_err(|_| {
+                        serde::de::Error::custom(format!("Couldn't deserialize method: {name}"))
+                    })
             }
         }
 
@@ -263,7 +392,7 @@ pub fn derive_method_num(name: &str) -> Result<MethodNum, error::Error> {
 #[cfg(test)]
 mod test {
     use super::{derive_method_num, pascal_case_split};
-    use crate::abi::{Abi, Method, MethodType};
+    use crate::abi::{Abi, Method, MethodType, ParamType};
 
     #[test]
     fn test_method_derivation() {
@@ -322,11 +451,15 @@ mod test {
                     number: derive_method_num(&test_transfer_name).unwrap(),
                     name: test_transfer_name,
                     r#type: MethodType::Test,
+                    params: vec![],
+                    value: 0,
                 },
                 Method {
                     number: derive_method_num(&test_transfer_fail_name).unwrap(),
                     name: test_transfer_fail_name,
                     r#type: MethodType::TestFail,
+                    params: vec![],
+                    value: 0,
                 },
             ],
         };
@@ -359,11 +492,15 @@ mod test {
                     number: derive_method_num(&test_transfer_name).unwrap(),
                     name: test_transfer_name,
                     r#type: MethodType::Test,
+                    params: vec![],
+                    value: 0,
                 },
                 Method {
                     number: 3280706483,
                     name: test_transfer_fail_name,
                     r#type: MethodType::TestFail,
+                    params: vec![],
+                    value: 0,
                 },
             ],
         };
@@ -398,6 +535,14 @@ mod test {
             Method::new_from_name("TestFailOne").unwrap().r#type,
             MethodType::TestFail
         );
+        assert_eq!(
+            Method::new_from_name("TestFuzzOne").unwrap().r#type,
+            MethodType::TestFuzz
+        );
+        assert_eq!(
+            Method::new_from_name("InvariantOne").unwrap().r#type,
+            MethodType::Invariant
+        );
         assert_eq!(
             Method::new_from_name("Constructor").unwrap().r#type,
             MethodType::Constructor
@@ -410,4 +555,72 @@ mod test {
         assert!(Method::new_from_name("testOne").is_err());
         assert!(Method::new_from_name("").is_err());
     }
+
+    #[test]
+    fn test_param_type_parsing() {
+        assert_eq!("u64".parse::<ParamType>().unwrap(), ParamType::U64);
+        assert_eq!(
+            "address[]".parse::<ParamType>().unwrap(),
+            ParamType::Array(Box::new(ParamType::Address))
+        );
+        assert_eq!(
+            "bytes[][]".parse::<ParamType>().unwrap(),
+            ParamType::Array(Box::new(ParamType::Array(Box::new(ParamType::Bytes))))
+        );
+        assert_eq!(
+            ParamType::Array(Box::new(ParamType::I64)).to_string(),
+            "i64[]"
+        );
+
+        match "u128".parse::<ParamType>() {
+            Ok(_) => panic!("u128 should not be a valid parameter type"),
+            Err(err) => assert_eq!(err.to_string(), "Parameter type `u128` is invalid"),
+        }
+    }
+
+    #[test]
+    fn test_params_serde() {
+        let abi = Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![Method::new_from_name("TestFuzzTransfer")
+                .unwrap()
+                .with_params(vec![
+                    ParamType::U64,
+                    ParamType::Array(Box::new(ParamType::Bool)),
+                ])],
+        };
+
+        let serialized_abi = crate::to_vec(&abi).unwrap();
+        let deserialized_abi: Abi = crate::from_slice(&serialized_abi).unwrap();
+        assert_eq!(deserialized_abi, abi);
+        assert_eq!(
+            deserialized_abi.methods()[0].params(),
+            &[ParamType::U64, ParamType::Array(Box::new(ParamType::Bool))]
+        );
+    }
+
+    #[test]
+    fn test_value_serde() {
+        let abi = Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![
+                Method::new_from_name("TestDeposit")
+                    .unwrap()
+                    .with_value(10u128.pow(24)),
+                Method::new_from_name("TestFuzzDeposit")
+                    .unwrap()
+                    .with_params(vec![ParamType::U64])
+                    .with_value(1000),
+            ],
+        };
+
+        let serialized_abi = crate::to_vec(&abi).unwrap();
+        let deserialized_abi: Abi = crate::from_slice(&serialized_abi).unwrap();
+        assert_eq!(deserialized_abi, abi);
+        assert_eq!(deserialized_abi.methods()[0].value(), 10u128.pow(24));
+        assert!(deserialized_abi.methods()[0].params().is_empty());
+        assert_eq!(deserialized_abi.methods()[1].value(), 1000);
+    }
 }
@@ -6,6 +6,8 @@
 pub enum Error {
     #[error("Method name `{0}` is invalid")]
     InvalidMethodName(String),
+    #[error("Parameter type `{0}` is invalid")]
+    InvalidParamType(String),
     #[error("Could not generate method number for `{name}`")]
     MethodNumberGeneration {
         name: String,
@@ -1,22 +1,48 @@
+use crate::context::{Prank, RandomnessEntry};
+use crate::expectations::{
+    format_event, CaughtRevert, ExpectationFailure, ExpectedCall, ExpectedEmit,
+    ExpectedProofVerification, ExpectedRevert,
+};
 use crate::kernel::KytheraKernel;
+use crate::keys::{account_actor_state, KeyType, TestKey};
 use crate::machine::KytheraMachine;
+use crate::mocks::{MockedCall, MockedConsensusFault, ProofKind};
+use crate::snapshot::StateSnapshot;
 use crate::utils::{
-    ALTER_NUM, CHAIN_ID_NUM, EPOCH_NUM, FEE_NUM, LOG_NUM, PRANK_NUM, TRICK_NUM, WARP_NUM,
+    ADDR_FROM_KEY_NUM, ALTER_NUM, ALTER_WITH_NUM, CHAIN_ID_NUM, CLEAR_MOCKED_CALLS_NUM, DEAL_NUM,
+    DEPLOY_NUM, EPOCH_NUM, EXPECT_CALL_NUM, EXPECT_EMIT_NUM, EXPECT_REVERT_NUM,
+    EXPECT_VERIFY_PROOF_NUM, FEE_NUM, FIXTURE_NUM, GAS_LIMIT_NUM, LOAD_BLOCK_NUM, LOAD_NUM,
+    LOG_NUM, MOCK_CALL_NUM, MOCK_CONSENSUS_FAULT_NUM, MOCK_PROOFS_NUM, MOCK_SIGNATURES_NUM,
+    NEW_ACCOUNT_NUM, PRANK_NUM, REVERT_TO_NUM, SET_BEACON_RANDOMNESS_NUM, SET_CHAIN_RANDOMNESS_NUM,
+    SET_TIPSET_CID_NUM, SIGN_NUM, SNAPSHOT_NUM, START_PRANK_NUM, STOP_PRANK_NUM, STORE_NUM,
+    TRICK_NUM, WARP_NUM,
 };
 use anyhow::anyhow;
+use cid::multihash::Code;
 use cid::Cid;
+use fvm::account_actor::State as AccountState;
 use fvm::call_manager::{CallManager, DefaultCallManager, FinishRet, InvocationResult};
 use fvm::engine::Engine;
 use fvm::gas::{Gas, GasTracker};
+use fvm::init_actor::{State as InitActorState, INIT_ACTOR_ID};
 use fvm::kernel::{Block, ExecutionError};
 use fvm::machine::Machine;
 use fvm::state_tree::ActorState;
 use fvm::Kernel;
-use fvm_ipld_encoding::from_slice;
+use fvm_ipld_blockstore::{Block as IpldBlock, Blockstore};
+use fvm_ipld_encoding::de::DeserializeOwned;
+use fvm_ipld_encoding::{from_slice, to_vec, CborStore, RawBytes, CBOR, DAG_CBOR};
 use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::consensus::{ConsensusFault, ConsensusFaultType};
 use fvm_shared::econ::TokenAmount;
-use fvm_shared::event::StampedEvent;
-use fvm_shared::{ActorID, MethodNum};
+use fvm_shared::error::ExitCode;
+use fvm_shared::event::{ActorEvent, StampedEvent};
+use fvm_shared::randomness::RANDOMNESS_LENGTH;
+use fvm_shared::{ActorID, MethodNum, IPLD_RAW};
+use libipld_core::ipld::Ipld;
+use libsecp256k1::SecretKey;
+use std::collections::HashSet;
 
 #[repr(transparent)]
 pub struct KytheraCallManager<C: CallManager = DefaultCallManager<KytheraMachine>>(pub C);
@@ -26,205 +52,1192 @@ where
     M: Machine,
     C: CallManager<Machine = KytheraMachine<M>>,
 {
-    fn handle_cheatcode(
+    /// Handle a call to the cheatcodes actor, returning the result the call should return if any.
+    fn handle_cheatcode<K: Kernel<CallManager = Self>>(
         &mut self,
         method: MethodNum,
         from: ActorID,
         params: Option<Block>,
-    ) -> fvm::kernel::Result<()> {
+    ) -> fvm::kernel::Result<Option<InvocationResult>> {
         match method {
             WARP_NUM => {
-                let new_timestamp: u64 = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Warp cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
-                    ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Warp cheatcode: {}",
-                        err
-                    )))
-                })?;
+                let new_timestamp: u64 = deserialize_cheatcode_params("Warp", params)?;
                 self.machine_mut().override_context.timestamp = Some(new_timestamp);
             }
             EPOCH_NUM => {
-                let new_epoch: i64 = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Epoch cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
-                    ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Epoch cheatcode: {}",
-                        err
-                    )))
-                })?;
-                self.machine_mut().override_context.epoch = Some(new_epoch);
+                let new_epoch: i64 = deserialize_cheatcode_params("Epoch", params)?;
+                self.machine_mut().set_epoch(new_epoch);
             }
             FEE_NUM => {
-                let (lo, hi): (u64, u64) = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Fee cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
-                    ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Fee cheatcode: {}",
-                        err
-                    )))
-                })?;
+                let (lo, hi): (u64, u64) = deserialize_cheatcode_params("Fee", params)?;
 
                 self.machine_mut().override_context.base_fee =
                     Some(fvm_shared::sys::TokenAmount { lo, hi });
             }
             CHAIN_ID_NUM => {
-                let chain_id: u64 = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for ChainId cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
-                    ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for ChainId cheatcode: {}",
-                        err
-                    )))
-                })?;
+                let chain_id: u64 = deserialize_cheatcode_params("ChainId", params)?;
 
                 self.machine_mut().override_context.chain_id = Some(chain_id);
             }
-            PRANK_NUM => {
-                let new_caller: Address = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Prank cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
-                    ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Prank cheatcode: {}",
-                        err
-                    )))
-                })?;
+            PRANK_NUM | START_PRANK_NUM => {
+                let cheatcode = if method == PRANK_NUM {
+                    "Prank"
+                } else {
+                    "StartPrank"
+                };
+                let (caller, origin) = deserialize_prank_params(cheatcode, params)?;
+
+                self.machine_mut().override_context.prank = Some(Prank {
+                    actor: from,
+                    caller,
+                    origin,
+                    persistent: method == START_PRANK_NUM,
+                });
+            }
+            STOP_PRANK_NUM => {
+                self.machine_mut().override_context.prank = None;
+            }
+            TRICK_NUM => {
+                let new_origin: Address = deserialize_cheatcode_params("Trick", params)?;
 
-                let new_caller_id = match new_caller.id() {
+                let new_origin_id = match new_origin.id() {
                     Ok(id) => id,
                     Err(err) => {
                         return Err(ExecutionError::Fatal(anyhow!(format!(
-                            "Address parameter for Prank should have a valid ActorID: {}",
+                            "Address parameter for Trick should have a valid ActorID: {}",
                             err
                         ))))
                     }
                 };
 
-                self.machine_mut().override_context.caller = Some(new_caller_id);
+                self.machine_mut().override_context.origin = Some(new_origin_id);
             }
-            TRICK_NUM => {
-                let new_origin: Address = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Trick cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
+            LOG_NUM => {
+                let message: String = deserialize_cheatcode_params("Log", params)?;
+
+                log::info!(target: "kythera-fvm::actors::logging", "Actor::{from}::log: {message}");
+            }
+            ALTER_NUM => {
+                let (target, cid_str): (Address, String) =
+                    deserialize_cheatcode_params("Alter", params)?;
+
+                let root = Cid::try_from(cid_str).map_err(|err| {
                     ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Trick cheatcode: {}",
+                        "Invalid Cid passed to Alter cheatcode: {}",
                         err
                     )))
                 })?;
+                self.alter_state(target, root, "Alter")?;
+            }
+            DEAL_NUM => {
+                let (target, balance): (Address, TokenAmount) =
+                    deserialize_cheatcode_params("Deal", params)?;
 
-                let new_origin_id = match new_origin.id() {
-                    Ok(id) => id,
-                    Err(err) => {
-                        return Err(ExecutionError::Fatal(anyhow!(format!(
-                            "Address parameter for Trick should have a valid ActorID: {}",
+                if balance.is_negative() {
+                    return Err(ExecutionError::Fatal(anyhow!(
+                        "Balance parameter for Deal should not be negative"
+                    )));
+                }
+
+                let target_id = self
+                    .resolve_address(&target)
+                    .map_err(|err| {
+                        ExecutionError::Fatal(anyhow!(format!(
+                            "Could not resolve target actor ID for Deal cheatcode: {}",
                             err
-                        ))))
+                        )))
+                    })?
+                    .ok_or(ExecutionError::Fatal(anyhow!(
+                        "No actor ID associated with target for Deal cheatcode"
+                    )))?;
+
+                let state = self
+                    .get_actor(target_id)
+                    .map_err(|err| {
+                        ExecutionError::Fatal(anyhow!(format!(
+                            "Could not get actor at given target address for Deal cheatcode: {}",
+                            err
+                        )))
+                    })?
+                    .ok_or_else(|| {
+                        ExecutionError::Fatal(anyhow!(
+                            "No actor at target address for Deal cheatcode"
+                        ))
+                    })?;
+
+                self.deal(target_id, state, balance)?;
+            }
+            EXPECT_REVERT_NUM => {
+                let (exit_code, message): (ExitCode, Option<String>) =
+                    deserialize_cheatcode_params("ExpectRevert", params)?;
+
+                if exit_code.is_success() {
+                    return Err(ExecutionError::Fatal(anyhow!(
+                        "Exit code parameter for ExpectRevert should not be a success"
+                    )));
+                }
+
+                self.machine_mut().expected_revert = Some(ExpectedRevert {
+                    actor: from,
+                    exit_code,
+                    message,
+                });
+            }
+            EXPECT_EMIT_NUM => {
+                let event: ActorEvent = deserialize_cheatcode_params("ExpectEmit", params)?;
+
+                if event.entries.is_empty() {
+                    return Err(ExecutionError::Fatal(anyhow!(
+                        "Event parameter for ExpectEmit should have at least one entry"
+                    )));
+                }
+
+                // Successive expected events of an actor are checked against the same call.
+                match self.machine_mut().expected_emit {
+                    Some(ref mut expected) if expected.actor == from => expected.events.push(event),
+                    ref mut expected_emit => {
+                        *expected_emit = Some(ExpectedEmit {
+                            actor: from,
+                            events: vec![event],
+                        })
                     }
-                };
+                }
+            }
+            SNAPSHOT_NUM => {
+                let snapshot = self.snapshot_state()?;
+                let snapshot_id = self.machine().snapshots.len() as u64;
+                self.machine_mut().snapshots.push(snapshot);
 
-                self.machine_mut().override_context.origin = Some(new_origin_id);
+                let snapshot_id = to_vec(&snapshot_id).map_err(|err| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "Could not serialize snapshot ID for Snapshot cheatcode: {}",
+                        err
+                    )))
+                })?;
+                return Ok(Some(returned(Block::new(DAG_CBOR, snapshot_id))));
             }
-            LOG_NUM => {
-                let message: String = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Log cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
+            REVERT_TO_NUM => {
+                let snapshot_id: u64 = deserialize_cheatcode_params("RevertTo", params)?;
+
+                let snapshot = self
+                    .machine()
+                    .snapshots
+                    .get(snapshot_id as usize)
+                    .cloned()
+                    .ok_or(ExecutionError::Fatal(anyhow!(
+                        "No snapshot with given ID for RevertTo cheatcode"
+                    )))?;
+                self.restore_state(snapshot)?;
+            }
+            MOCK_CALL_NUM => {
+                let (to, method, params, return_bytes, exit_code): (
+                    Address,
+                    MethodNum,
+                    Option<RawBytes>,
+                    RawBytes,
+                    ExitCode,
+                ) = deserialize_cheatcode_params("MockCall", params)?;
+
+                // The mocked actor does not have to exist.
+                let to_id = self.resolve_address(&to)?;
+
+                // A new mock replaces the one with the same target, method and parameters.
+                let mocked_calls = &mut self.machine_mut().mocked_calls;
+                mocked_calls.retain(|mocked| {
+                    mocked.to != to || mocked.method != method || mocked.params != params
+                });
+                mocked_calls.push(MockedCall {
+                    to,
+                    to_id,
+                    method,
+                    params,
+                    return_bytes,
+                    exit_code,
+                });
+            }
+            CLEAR_MOCKED_CALLS_NUM => {
+                self.machine_mut().mocked_calls.clear();
+            }
+            EXPECT_CALL_NUM => {
+                let (to, method, params, value, count): (
+                    Address,
+                    MethodNum,
+                    Option<RawBytes>,
+                    Option<TokenAmount>,
+                    u64,
+                ) = deserialize_cheatcode_params("ExpectCall", params)?;
+
+                let to_id = self.resolve_address(&to)?;
+                self.machine_mut().expected_calls.push(ExpectedCall {
+                    to,
+                    to_id,
+                    method,
+                    params,
+                    value,
+                    count,
+                    calls: 0,
+                });
+            }
+            SET_CHAIN_RANDOMNESS_NUM => {
+                let entries = deserialize_randomness_params("SetChainRandomness", params)?;
+                self.machine_mut().override_context.chain_randomness = entries;
+            }
+            SET_BEACON_RANDOMNESS_NUM => {
+                let entries = deserialize_randomness_params("SetBeaconRandomness", params)?;
+                self.machine_mut().override_context.beacon_randomness = entries;
+            }
+            SET_TIPSET_CID_NUM => {
+                let (epoch, cid_str): (ChainEpoch, String) =
+                    deserialize_cheatcode_params("SetTipsetCid", params)?;
+
+                let cid = Cid::try_from(cid_str).map_err(|err| {
                     ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Log cheatcode: {}",
+                        "Invalid Cid passed to SetTipsetCid cheatcode: {}",
                         err
                     )))
                 })?;
+                self.machine_mut()
+                    .override_context
+                    .tipset_cids
+                    .insert(epoch, cid);
+            }
+            MOCK_CONSENSUS_FAULT_NUM => {
+                let (h1, h2, extra, fault): (
+                    RawBytes,
+                    RawBytes,
+                    RawBytes,
+                    Option<(Address, ChainEpoch, u8)>,
+                ) = deserialize_cheatcode_params("MockConsensusFault", params)?;
 
-                log::info!(target: "kythera-fvm::actors::logging", "Actor::{from}::log: {message}");
+                let fault = fault
+                    .map(|(target, epoch, fault_type)| {
+                        if target.id().is_err() {
+                            return Err(ExecutionError::Fatal(anyhow!(
+                                "Target address for MockConsensusFault cheatcode should be an ID address"
+                            )));
+                        }
+                        let fault_type = match fault_type {
+                            1 => ConsensusFaultType::DoubleForkMining,
+                            2 => ConsensusFaultType::ParentGrinding,
+                            3 => ConsensusFaultType::TimeOffsetMining,
+                            _ => {
+                                return Err(ExecutionError::Fatal(anyhow!(format!(
+                                    "Invalid fault type passed to MockConsensusFault cheatcode: {}",
+                                    fault_type
+                                ))))
+                            }
+                        };
+                        Ok(ConsensusFault {
+                            target,
+                            epoch,
+                            fault_type,
+                        })
+                    })
+                    .transpose()?;
+
+                // A new mock replaces the one with the same evidence.
+                let (h1, h2, extra) = (h1.to_vec(), h2.to_vec(), extra.to_vec());
+                let mocked_faults = &mut self.machine_mut().mocked_consensus_faults;
+                mocked_faults
+                    .retain(|mocked| mocked.h1 != h1 || mocked.h2 != h2 || mocked.extra != extra);
+                mocked_faults.push(MockedConsensusFault {
+                    h1,
+                    h2,
+                    extra,
+                    fault,
+                });
             }
-            ALTER_NUM => {
-                let (target, cid_str): (Address, String) = from_slice(
-                    params
-                        .ok_or(ExecutionError::Fatal(anyhow!(
-                            "No parameters provided for Alter cheatcode"
-                        )))?
-                        .data(),
-                )
-                .map_err(|err| {
+            MOCK_PROOFS_NUM => {
+                let mock_proofs: bool = deserialize_cheatcode_params("MockProofs", params)?;
+
+                self.machine_mut().mock_proofs = mock_proofs;
+            }
+            EXPECT_VERIFY_PROOF_NUM => {
+                let (kind, verdict): (String, bool) =
+                    deserialize_cheatcode_params("ExpectVerifyProof", params)?;
+
+                let kind = ProofKind::from_name(&kind).ok_or_else(|| {
                     ExecutionError::Fatal(anyhow!(format!(
-                        "Could not deserialize parameters for Alter cheatcode: {}",
+                        "Invalid proof kind passed to ExpectVerifyProof cheatcode: {}",
+                        kind
+                    )))
+                })?;
+                self.machine_mut()
+                    .expected_proof_verifications
+                    .get_mut()
+                    .push(ExpectedProofVerification { kind, verdict });
+            }
+            NEW_ACCOUNT_NUM => {
+                let (label, balance, key_type) = deserialize_new_account_params(params)?;
+
+                let address = self.create_keyed_account(
+                    TestKey::from_label(&label, key_type),
+                    Some(label),
+                    balance,
+                )?;
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&address).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            ADDR_FROM_KEY_NUM => {
+                let key: RawBytes = deserialize_cheatcode_params("AddrFromKey", params)?;
+
+                let secret_key = SecretKey::parse_slice(key.bytes()).map_err(|err| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "Invalid private key passed to AddrFromKey cheatcode: {}",
                         err
                     )))
                 })?;
+                let address =
+                    self.create_keyed_account(TestKey::Secp256k1(secret_key), None, None)?;
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&address).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            SIGN_NUM => {
+                let (account, digest): (Address, RawBytes) =
+                    deserialize_cheatcode_params("Sign", params)?;
+
+                let key_address = self.key_address(account)?;
+                let key = self.machine().keyring.key(&key_address).ok_or_else(|| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "No key managed for account {} in Sign cheatcode",
+                        account
+                    )))
+                })?;
+                let signature = key
+                    .sign(digest.bytes())
+                    .ok_or(ExecutionError::Fatal(anyhow!(
+                        "Digest parameter for Sign cheatcode should be 32 bytes long"
+                    )))?;
+
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&RawBytes::new(signature))
+                        .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            MOCK_SIGNATURES_NUM => {
+                let verdict: Option<bool> = deserialize_cheatcode_params("MockSignatures", params)?;
+
+                self.machine_mut().mock_signatures = verdict;
+            }
+            DEPLOY_NUM => {
+                let (actor, constructor_params, balance): (String, RawBytes, TokenAmount) =
+                    deserialize_cheatcode_params("Deploy", params)?;
+
+                if balance.is_negative() {
+                    return Err(ExecutionError::Fatal(anyhow!(
+                        "Balance parameter for Deploy should not be negative"
+                    )));
+                }
+
+                return self
+                    .deploy_actor::<K>(&actor, constructor_params, balance)
+                    .map(Some);
+            }
+            GAS_LIMIT_NUM => {
+                let gas_limit: u64 = deserialize_cheatcode_params("GasLimit", params)?;
+
+                let machine = self.machine_mut();
+                machine.gas_limit = Some(gas_limit);
+                machine.gas_limited = true;
+            }
+            LOAD_NUM => {
+                let target: Address = deserialize_cheatcode_params("Load", params)?;
 
                 let target_id = self
                     .resolve_address(&target)
                     .map_err(|err| {
                         ExecutionError::Fatal(anyhow!(format!(
-                            "Could not resolve target actor ID for Alter cheatcode: {}",
+                            "Could not resolve target actor ID for Load cheatcode: {}",
                             err
                         )))
                     })?
                     .ok_or(ExecutionError::Fatal(anyhow!(
-                        "No actor ID associated with target for Alter cheatcode"
+                        "No actor ID associated with target for Load cheatcode"
                     )))?;
 
-                let mut state = self
+                let state = self
                     .get_actor(target_id)
                     .map_err(|err| {
                         ExecutionError::Fatal(anyhow!(format!(
-                            "Could not get actor at given target address for Alter cheatcode: {}",
+                            "Could not get actor at given target address for Load cheatcode: {}",
                             err
                         )))
                     })?
                     .ok_or_else(|| {
                         ExecutionError::Fatal(anyhow!(
-                            "No actor at target address for Alter cheatcode"
+                            "No actor at target address for Load cheatcode"
                         ))
                     })?;
 
-                state.state = Cid::try_from(cid_str).map_err(|err| {
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&state.state).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            LOAD_BLOCK_NUM => {
+                let cid: Cid = deserialize_cheatcode_params("LoadBlock", params)?;
+
+                let block = self
+                    .machine()
+                    .blockstore()
+                    .get(&cid)
+                    .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?
+                    .ok_or_else(|| {
+                        ExecutionError::Fatal(anyhow!(format!(
+                            "No block found for Cid {} in LoadBlock cheatcode",
+                            cid
+                        )))
+                    })?;
+
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&RawBytes::new(block))
+                        .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            STORE_NUM => {
+                let (codec, data): (u64, RawBytes) = deserialize_cheatcode_params("Store", params)?;
+
+                if ![CBOR, DAG_CBOR, IPLD_RAW].contains(&codec) {
+                    return Err(ExecutionError::Fatal(anyhow!(format!(
+                        "Unsupported codec {} passed to Store cheatcode",
+                        codec
+                    ))));
+                }
+
+                let cid = self.store_block(codec, data.bytes())?;
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&cid).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            ALTER_WITH_NUM => {
+                let (target, state): (Address, RawBytes) =
+                    deserialize_cheatcode_params("AlterWith", params)?;
+
+                let root = self.store_block(DAG_CBOR, state.bytes())?;
+                self.alter_state(target, root, "AlterWith")?;
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&root).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
+            }
+            FIXTURE_NUM => {
+                let name: String = deserialize_cheatcode_params("Fixture", params)?;
+
+                let id = *self.machine().fixtures.get(&name).ok_or_else(|| {
                     ExecutionError::Fatal(anyhow!(format!(
-                        "Invalid Cid passed to Alter cheatcode: {}",
-                        err
+                        "No fixture actor named {} for Fixture cheatcode",
+                        name
                     )))
                 })?;
-                self.set_actor(target_id, state)?;
+
+                return Ok(Some(returned(Block::new(
+                    DAG_CBOR,
+                    to_vec(&id).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+                ))));
             }
             _ => return Err(ExecutionError::Fatal(anyhow!("Call to unknown cheatcode"))),
         }
 
+        Ok(None)
+    }
+
+    /// Put a block into the machine blockstore, returning its CID.
+    fn store_block(&self, codec: u64, data: &[u8]) -> fvm::kernel::Result<Cid> {
+        self.machine()
+            .blockstore()
+            .put(Code::Blake2b256, &IpldBlock { codec, data })
+            .map_err(|err| ExecutionError::Fatal(anyhow!(err)))
+    }
+
+    /// Replace the state root of a given actor, after checking that every block of the new state
+    /// is in the blockstore, the blocks read being charged as gas. Warns if the new state does
+    /// not have the shape of the current state of the actor, which its code may fail to load.
+    fn alter_state(
+        &mut self,
+        target: Address,
+        root: Cid,
+        cheatcode: &str,
+    ) -> fvm::kernel::Result<()> {
+        let target_id = self
+            .resolve_address(&target)
+            .map_err(|err| {
+                ExecutionError::Fatal(anyhow!(format!(
+                    "Could not resolve target actor ID for {} cheatcode: {}",
+                    cheatcode, err
+                )))
+            })?
+            .ok_or(ExecutionError::Fatal(anyhow!(format!(
+                "No actor ID associated with target for {} cheatcode",
+                cheatcode
+            ))))?;
+
+        let mut state = self
+            .get_actor(target_id)
+            .map_err(|err| {
+                ExecutionError::Fatal(anyhow!(format!(
+                    "Could not get actor at given target address for {} cheatcode: {}",
+                    cheatcode, err
+                )))
+            })?
+            .ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!(format!(
+                    "No actor at target address for {} cheatcode",
+                    cheatcode
+                )))
+            })?;
+
+        // Walk the new state to make sure the actor can load all of it, charging each block read
+        // so that the walk is bounded by the gas of the message.
+        let mut visited = HashSet::new();
+        let mut links = vec![root];
+        while let Some(cid) = links.pop() {
+            if !visited.insert(cid) {
+                continue;
+            }
+            let _ = self.charge_gas(self.price_list().on_block_open_base())?;
+            let block = self
+                .machine()
+                .blockstore()
+                .get(&cid)
+                .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?
+                .ok_or_else(|| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "Block {} of the new state is not in the blockstore for {} cheatcode",
+                        cid, cheatcode
+                    )))
+                })?;
+            let _ = self.charge_gas(self.price_list().on_block_open_per_byte(block.len()))?;
+            if cid.codec() == DAG_CBOR {
+                let ipld: Ipld = from_slice(&block).map_err(|err| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "Could not decode block {} of the new state for {} cheatcode: {}",
+                        cid, cheatcode, err
+                    )))
+                })?;
+                ipld.references(&mut links);
+            }
+        }
+
+        let current: Option<Ipld> = self
+            .machine()
+            .blockstore()
+            .get_cbor(&state.state)
+            .ok()
+            .flatten();
+        let new: Option<Ipld> = self.machine().blockstore().get_cbor(&root).ok().flatten();
+        if let (Some(current), Some(new)) = (current, new) {
+            if !matches_state_shape(&current, &new) {
+                log::warn!(
+                    "New state {} of actor {} does not have the shape of its current state {}, \
+                     its code {} may fail to load it",
+                    root,
+                    target,
+                    state.state,
+                    state.code
+                );
+            }
+        }
+
+        state.state = root;
+        self.set_actor(target_id, state)
+    }
+
+    /// Deploy a registered actor, found by name or code CID, provided with a given balance. Its
+    /// constructor is called by the init actor, and a failing constructor reverts the deployment.
+    /// Returns the ID address of the new actor, or the result of its failing constructor.
+    fn deploy_actor<K: Kernel<CallManager = Self>>(
+        &mut self,
+        actor: &str,
+        constructor_params: RawBytes,
+        balance: TokenAmount,
+    ) -> fvm::kernel::Result<InvocationResult> {
+        let registry = &self.machine().registry;
+        let registered = registry
+            .get(actor)
+            .or_else(|| {
+                Cid::try_from(actor)
+                    .ok()
+                    .and_then(|code| registry.get_by_code(&code))
+            })
+            .copied()
+            .ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!(format!(
+                    "Unknown actor passed to Deploy cheatcode: {}",
+                    actor
+                )))
+            })?;
+
+        self.with_transaction(|cm| {
+            // The actor is created as done by the init actor, with the robust address of the next
+            // actor created by the message.
+            let address = cm.next_actor_address();
+            let id = cm
+                .machine_mut()
+                .state_tree_mut()
+                .register_new_address(&address)?;
+            cm.create_actor(registered.code, id, None)?;
+            let state = cm.get_actor(id)?.ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!("Deployed actor {} should exist", actor))
+            })?;
+            cm.deal(id, state, balance)?;
+
+            // The constructor is sent like any other call, so that it can be mocked or expected.
+            let ret = match registered.constructor {
+                Some(constructor) => {
+                    let params = (!constructor_params.is_empty())
+                        .then(|| Block::new(DAG_CBOR, constructor_params.to_vec()));
+                    cm.send::<K>(
+                        INIT_ACTOR_ID,
+                        Address::new_id(id),
+                        constructor,
+                        params,
+                        &TokenAmount::default(),
+                        None,
+                        false,
+                    )?
+                }
+                None => InvocationResult::default(),
+            };
+            if !ret.exit_code.is_success() {
+                return Ok(ret);
+            }
+
+            Ok(returned(Block::new(
+                DAG_CBOR,
+                to_vec(&Address::new_id(id)).map_err(|err| ExecutionError::Fatal(anyhow!(err)))?,
+            )))
+        })
+    }
+
+    /// Create the account of a key if it does not exist, and manage the key for the `Sign`
+    /// cheatcode. The account is labelled and dealt the balance if any. Returns the key address of
+    /// the account.
+    fn create_keyed_account(
+        &mut self,
+        key: TestKey,
+        label: Option<String>,
+        balance: Option<TokenAmount>,
+    ) -> fvm::kernel::Result<Address> {
+        let state = account_actor_state(
+            &key,
+            self.machine().builtin_actors(),
+            self.machine().blockstore(),
+            TokenAmount::default(),
+        )
+        .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?;
+        let address = self.machine_mut().keyring.add_key(key);
+
+        let id = match self.resolve_address(&address)? {
+            Some(id) => id,
+            None => {
+                let id = self
+                    .machine_mut()
+                    .state_tree_mut()
+                    .register_new_address(&address)?;
+                self.set_actor(id, state)?;
+                id
+            }
+        };
+        if let Some(label) = label {
+            self.machine_mut().keyring.set_label(id, label);
+        }
+        if let Some(balance) = balance {
+            let state = self.get_actor(id)?.ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!(format!("No actor at address {}", address)))
+            })?;
+            self.deal(id, state, balance)?;
+        }
+
+        Ok(address)
+    }
+
+    /// Set the balance of an actor, adding the dealt tokens to or removing them from the
+    /// circulating supply.
+    fn deal(
+        &mut self,
+        id: ActorID,
+        mut state: ActorState,
+        balance: TokenAmount,
+    ) -> fvm::kernel::Result<()> {
+        self.machine_mut().circ_supply_delta += &balance - &state.balance;
+
+        state.balance = balance;
+        self.set_actor(id, state)
+    }
+
+    /// Get the key address of an account, loading it from the account state for ID addresses.
+    fn key_address(&self, account: Address) -> fvm::kernel::Result<Address> {
+        let id = match account.id() {
+            Ok(id) => id,
+            Err(_) => return Ok(account),
+        };
+        let actor = self.get_actor(id)?.ok_or_else(|| {
+            ExecutionError::Fatal(anyhow!(format!("No actor at address {}", account)))
+        })?;
+        if actor.code == *self.machine().builtin_actors().get_ethaccount_code() {
+            return actor.delegated_address.ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!(format!(
+                    "No delegated address for account {}",
+                    account
+                )))
+            });
+        }
+        if actor.code != *self.machine().builtin_actors().get_account_code() {
+            return Err(ExecutionError::Fatal(anyhow!(format!(
+                "Actor at address {} is not an account",
+                account
+            ))));
+        }
+        let state: AccountState = self
+            .machine()
+            .blockstore()
+            .get_cbor(&actor.state)
+            .map_err(|err| ExecutionError::Fatal(anyhow!(err)))?
+            .ok_or_else(|| {
+                ExecutionError::Fatal(anyhow!(format!("No state for account {}", account)))
+            })?;
+        Ok(state.address)
+    }
+
+    /// Record the state of every actor that has been assigned an ID.
+    fn snapshot_state(&self) -> fvm::kernel::Result<StateSnapshot> {
+        let (init_state, _) = InitActorState::load(self.machine().state_tree())?;
+        let actors = (0..init_state.next_id)
+            .map(|id| self.get_actor(id))
+            .collect::<fvm::kernel::Result<Vec<Option<ActorState>>>>()?;
+
+        Ok(StateSnapshot {
+            actors,
+            circ_supply_delta: self.machine().circ_supply_delta.clone(),
+        })
+    }
+
+    /// Restore the state of the actors to the one recorded in a snapshot, deleting the actors
+    /// created since.
+    fn restore_state(&mut self, snapshot: StateSnapshot) -> fvm::kernel::Result<()> {
+        let (init_state, _) = InitActorState::load(self.machine().state_tree())?;
+        for id in 0..init_state.next_id {
+            let actor = snapshot.actors.get(id as usize).cloned().flatten();
+            if self.get_actor(id)? == actor {
+                continue;
+            }
+            match actor {
+                Some(actor) => self.set_actor(id, actor)?,
+                None => self.delete_actor(id)?,
+            }
+        }
+
+        self.machine_mut().circ_supply_delta = snapshot.circ_supply_delta;
+        Ok(())
+    }
+
+    /// Get the result of a call mocked by the `MockCall` cheatcode, mocks with matching parameters
+    /// taking precedence over the ones matching any parameters.
+    fn mocked_call(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<&Block>,
+    ) -> fvm::kernel::Result<Option<InvocationResult>> {
+        let mocked_calls = &self.machine().mocked_calls;
+        if mocked_calls.is_empty() {
+            return Ok(None);
+        }
+
+        let to_id = self.resolve_address(to)?;
+        let params = params.map(|params| params.data()).unwrap_or_default();
+        let mut mocks = mocked_calls.iter().filter(|mocked| {
+            mocked.method == method
+                && (&mocked.to == to || (mocked.to_id.is_some() && mocked.to_id == to_id))
+        });
+        let mocked = mocks
+            .clone()
+            .find(|mocked| matches!(mocked.params, Some(ref mocked) if mocked.bytes() == params))
+            .or_else(|| mocks.find(|mocked| mocked.params.is_none()));
+
+        Ok(mocked.map(|mocked| InvocationResult {
+            exit_code: mocked.exit_code,
+            value: (!mocked.return_bytes.is_empty())
+                .then(|| Block::new(DAG_CBOR, mocked.return_bytes.bytes())),
+        }))
+    }
+
+    /// Count a call that succeeded against the ones expected by the `ExpectCall` cheatcode. The
+    /// count is restored if a transaction the call is part of reverts.
+    fn record_expected_calls(
+        &mut self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<&Block>,
+        value: &TokenAmount,
+    ) -> fvm::kernel::Result<()> {
+        if self.machine().expected_calls.is_empty() {
+            return Ok(());
+        }
+
+        let to_id = self.resolve_address(to)?;
+        let params = params.map(|params| params.data()).unwrap_or_default();
+        for expected in self.machine_mut().expected_calls.iter_mut() {
+            if expected.matches(to, to_id, method, params, value) {
+                expected.calls += 1;
+            }
+        }
         Ok(())
     }
+
+    /// Fail the message as out of gas if it used more gas than the limit set by the `GasLimit`
+    /// cheatcode. The limit is enforced during the execution of the actors by the kernel, this
+    /// catches the gas charged after the last actor execution.
+    fn check_gas_limit(
+        &self,
+        result: fvm::kernel::Result<InvocationResult>,
+    ) -> fvm::kernel::Result<InvocationResult> {
+        match self.machine().gas_limit {
+            Some(gas_limit) if self.gas_tracker().gas_used() > Gas::new(gas_limit) => {
+                Err(ExecutionError::OutOfGas)
+            }
+            _ => result,
+        }
+    }
+
+    /// Check the result of a call against the revert expected by the `ExpectRevert` cheatcode.
+    fn check_expected_revert(
+        &mut self,
+        expected: ExpectedRevert,
+        to: Address,
+        method: MethodNum,
+        result: fvm::kernel::Result<InvocationResult>,
+    ) -> fvm::kernel::Result<InvocationResult> {
+        match result {
+            Ok(ret) if ret.exit_code == expected.exit_code => {
+                if let Some(message) = expected.message {
+                    let source = self.resolve_address(&to)?.ok_or_else(|| {
+                        ExecutionError::Fatal(anyhow!(
+                            "No actor ID associated with reverted call for ExpectRevert cheatcode"
+                        ))
+                    })?;
+                    self.machine_mut().caught_reverts.push(CaughtRevert {
+                        source,
+                        method,
+                        exit_code: ret.exit_code,
+                        message,
+                    });
+                }
+                self.machine_mut().revert_caught = true;
+                Ok(ret)
+            }
+            Ok(ret) if ret.exit_code.is_success() => Err(self.fail_expectation(format!(
+                "Call to {} (method {}) did not revert, expected exit code {}",
+                to, method, expected.exit_code
+            ))),
+            Ok(ret) => Err(self.fail_expectation(format!(
+                "Call to {} (method {}) reverted with exit code {}, expected exit code {}",
+                to, method, ret.exit_code, expected.exit_code
+            ))),
+            Err(ExecutionError::Syscall(err)) => Err(self.fail_expectation(format!(
+                "Call to {} (method {}) failed with {}, expected exit code {}",
+                to, method, err, expected.exit_code
+            ))),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Check the events emitted during a call against the ones expected by the `ExpectEmit`
+    /// cheatcode.
+    fn check_expected_emit(
+        &mut self,
+        expected: ExpectedEmit,
+        to: Address,
+        method: MethodNum,
+        first_event: usize,
+        result: fvm::kernel::Result<InvocationResult>,
+    ) -> fvm::kernel::Result<InvocationResult> {
+        let ret = result?;
+
+        // Events of a failed call are reverted with its state changes.
+        let emitted: &[StampedEvent] = if ret.exit_code.is_success() {
+            &self.machine().emitted_events[first_event..]
+        } else {
+            &[]
+        };
+
+        // Expected events should be emitted in order, other events can be emitted in between.
+        let mut emitted = emitted.iter();
+        let missing = expected
+            .events
+            .iter()
+            .find(|event| !emitted.any(|emitted| &emitted.event == *event));
+        match missing {
+            Some(event) => Err(self.fail_expectation(format!(
+                "Call to {} (method {}) did not emit expected event {}",
+                to,
+                method,
+                format_event(event)
+            ))),
+            None => Ok(ret),
+        }
+    }
+
+    /// Record an expectation that was not met, failing the message once it is applied, and get
+    /// the error aborting the message.
+    fn fail_expectation(&mut self, message: String) -> ExecutionError {
+        let failure = &mut self.machine_mut().expectation_failure;
+        if failure.is_none() {
+            *failure = Some(message.clone());
+        }
+        ExecutionError::Fatal(anyhow!(message))
+    }
+}
+
+/// Check the expectations that can only be checked once the message backtrace is available,
+/// returning the first one that was not met.
+fn check_finished_expectations(
+    ret: &FinishRet,
+    expected_revert: Option<ExpectedRevert>,
+    caught_reverts: Vec<CaughtRevert>,
+    expected_emit: Option<ExpectedEmit>,
+    expected_calls: &[ExpectedCall],
+    expected_proof_verifications: &[ExpectedProofVerification],
+) -> Option<String> {
+    // A test actor aborting before its next call already fails.
+    if let Some(expected) = expected_revert.filter(|expected| {
+        ret.backtrace
+            .frames
+            .iter()
+            .all(|f| f.source != expected.actor)
+    }) {
+        return Some(format!(
+            "Actor {} expected a revert with exit code {} but made no call",
+            Address::new_id(expected.actor),
+            expected.exit_code
+        ));
+    }
+    if let Some(expected) = expected_emit.filter(|expected| {
+        ret.backtrace
+            .frames
+            .iter()
+            .all(|f| f.source != expected.actor)
+    }) {
+        return Some(format!(
+            "Actor {} expected {} event(s) to be emitted but made no call",
+            Address::new_id(expected.actor),
+            expected.events.len()
+        ));
+    }
+
+    // Abort messages are only available in the backtrace of the message: FVM keeps them in the
+    // private backtrace of its call manager, so they cannot be read when the revert is caught.
+    // The backtrace is restarted when an actor aborts right after a failing syscall, dropping
+    // the frames of the reverts caught before.
+    let mut frames = ret.backtrace.frames.iter();
+    for caught in caught_reverts {
+        let frame = frames.find(|frame| {
+            frame.source == caught.source
+                && frame.method == caught.method
+                && frame.code == caught.exit_code
+        });
+        match frame {
+            Some(frame) if !frame.message.contains(&caught.message) => {
+                return Some(format!(
+                    "Call to {} (method {}) reverted with message \"{}\", expected \"{}\"",
+                    Address::new_id(caught.source),
+                    caught.method,
+                    frame.message,
+                    caught.message
+                ))
+            }
+            Some(_) => {}
+            None if ret.backtrace.cause.is_some() => {
+                return Some(format!(
+                    "Could not check the abort message of the call to {} (method {}), expected \"{}\": the backtrace was restarted by an abort following a failing syscall",
+                    Address::new_id(caught.source),
+                    caught.method,
+                    caught.message
+                ))
+            }
+            None => {
+                return Some(format!(
+                    "Could not retrieve the abort message of the call to {} (method {}), expected \"{}\"",
+                    Address::new_id(caught.source),
+                    caught.method,
+                    caught.message
+                ))
+            }
+        }
+    }
+
+    // Only the calls that were not reverted count, whatever the result of the message.
+    if let Some(expected) = expected_calls
+        .iter()
+        .find(|expected| expected.calls != expected.count)
+    {
+        return Some(format!(
+            "Expected {} call(s) to {} (method {}) but {} were sent",
+            expected.count, expected.to, expected.method, expected.calls
+        ));
+    }
+
+    expected_proof_verifications.first().map(|expected| {
+        format!(
+            "Expected {} proof verification(s) but {} proof was not verified",
+            expected_proof_verifications.len(),
+            expected.kind.name()
+        )
+    })
+}
+
+/// Check that a new state has the same shape as the current state of an actor: same kinds of
+/// values, lists of the same length and maps with the same keys. Null values match any value,
+/// as optional fields can be set or not. The actor code is not inspected, the current state
+/// standing for the schema it expects.
+fn matches_state_shape(current: &Ipld, new: &Ipld) -> bool {
+    match (current, new) {
+        (Ipld::Null, _) | (_, Ipld::Null) => true,
+        (Ipld::List(current), Ipld::List(new)) => {
+            current.len() == new.len()
+                && current
+                    .iter()
+                    .zip(new)
+                    .all(|(current, new)| same_kind(current, new))
+        }
+        (Ipld::Map(current), Ipld::Map(new)) => current.keys().eq(new.keys()),
+        (current, new) => same_kind(current, new),
+    }
+}
+
+/// Check that two values are of the same kind, null values matching any kind.
+fn same_kind(current: &Ipld, new: &Ipld) -> bool {
+    matches!(current, Ipld::Null)
+        || matches!(new, Ipld::Null)
+        || std::mem::discriminant(current) == std::mem::discriminant(new)
+}
+
+/// Deserialize the parameters of a cheatcode into given type.
+/// Result of a cheatcode successfully returning a value.
+fn returned(value: Block) -> InvocationResult {
+    InvocationResult {
+        exit_code: ExitCode::OK,
+        value: Some(value),
+    }
+}
+
+fn deserialize_cheatcode_params<T: DeserializeOwned>(
+    cheatcode: &str,
+    params: Option<Block>,
+) -> fvm::kernel::Result<T> {
+    let params = params.ok_or(ExecutionError::Fatal(anyhow!(format!(
+        "No parameters provided for {} cheatcode",
+        cheatcode
+    ))))?;
+
+    from_slice(params.data()).map_err(|err| {
+        ExecutionError::Fatal(anyhow!(format!(
+            "Could not deserialize parameters for {} cheatcode: {}",
+            cheatcode, err
+        )))
+    })
+}
+
+/// Deserialize the parameters of a cheatcode accepting either its CBOR array of parameters or its
+/// first parameter alone, converted with `from_single`. The two shapes are told apart by the CBOR
+/// major type of the parameters, so that the decoding error of the shape sent is reported.
+fn deserialize_cheatcode_params_or<T: DeserializeOwned, S: DeserializeOwned>(
+    cheatcode: &str,
+    params: Option<Block>,
+    from_single: impl FnOnce(S) -> T,
+) -> fvm::kernel::Result<T> {
+    // CBOR arrays have the major type 4, held by the 3 high bits of their first byte.
+    let is_array = params
+        .as_ref()
+        .and_then(|params| params.data().first())
+        .is_some_and(|byte| byte >> 5 == 4);
+    if is_array {
+        deserialize_cheatcode_params(cheatcode, params)
+    } else {
+        deserialize_cheatcode_params(cheatcode, params).map(from_single)
+    }
+}
+
+/// Deserialize the parameters of a prank cheatcode, either a caller address or a caller address
+/// and an optional origin address, into actor IDs.
+fn deserialize_prank_params(
+    cheatcode: &str,
+    params: Option<Block>,
+) -> fvm::kernel::Result<(ActorID, Option<ActorID>)> {
+    let (new_caller, new_origin): (Address, Option<Address>) =
+        deserialize_cheatcode_params_or(cheatcode, params, |new_caller| (new_caller, None))?;
+
+    let actor_id = |address: Address| {
+        address.id().map_err(|err| {
+            ExecutionError::Fatal(anyhow!(format!(
+                "Address parameter for {} should have a valid ActorID: {}",
+                cheatcode, err
+            )))
+        })
+    };
+    Ok((actor_id(new_caller)?, new_origin.map(actor_id).transpose()?))
+}
+
+/// Deserialize the parameters of the NewAccount cheatcode, either a label or a label, a balance
+/// and a key type.
+fn deserialize_new_account_params(
+    params: Option<Block>,
+) -> fvm::kernel::Result<(String, Option<TokenAmount>, KeyType)> {
+    let (label, balance, key_type): (String, Option<TokenAmount>, String) =
+        deserialize_cheatcode_params_or("NewAccount", params, |label| {
+            (label, None, String::from("Secp256k1"))
+        })?;
+
+    if balance.as_ref().is_some_and(TokenAmount::is_negative) {
+        return Err(ExecutionError::Fatal(anyhow!(
+            "Balance parameter for NewAccount should not be negative"
+        )));
+    }
+    let key_type = KeyType::from_name(&key_type).ok_or_else(|| {
+        ExecutionError::Fatal(anyhow!(format!(
+            "Invalid key type passed to NewAccount cheatcode: {}",
+            key_type
+        )))
+    })?;
+
+    Ok((label, balance, key_type))
+}
+
+/// Deserialize the parameters of a randomness cheatcode, either a fixed randomness or a table of
+/// optional domain separation tag, optional epoch and randomness entries.
+fn deserialize_randomness_params(
+    cheatcode: &str,
+    params: Option<Block>,
+) -> fvm::kernel::Result<Vec<RandomnessEntry>> {
+    let entries: Vec<(Option<i64>, Option<ChainEpoch>, RawBytes)> =
+        deserialize_cheatcode_params_or(cheatcode, params, |randomness| {
+            vec![(None, None, randomness)]
+        })?;
+
+    entries
+        .into_iter()
+        .map(|(dst, epoch, randomness)| {
+            let randomness =
+                <[u8; RANDOMNESS_LENGTH]>::try_from(randomness.bytes()).map_err(|_| {
+                    ExecutionError::Fatal(anyhow!(format!(
+                        "Randomness parameter for {} should be {} bytes long",
+                        cheatcode, RANDOMNESS_LENGTH
+                    )))
+                })?;
+            Ok(RandomnessEntry {
+                dst,
+                epoch,
+                randomness,
+            })
+        })
+        .collect()
 }
 
 impl<M, C> CallManager for KytheraCallManager<C>
@@ -268,19 +1281,103 @@ where
         gas_limit: Option<Gas>,
         read_only: bool,
     ) -> fvm::kernel::Result<InvocationResult> {
+        // The executor sends messages as writable calls, the first send of a message is the call
+        // to its recipient.
+        let read_only = read_only || std::mem::take(&mut self.machine_mut().read_only);
+
         // If cheatcode actor then we proceed as usual
         if to == Address::new_id(98) {
-            self.handle_cheatcode(method, from, params.clone())?;
+            // A deployment is checked against the revert expected by the deploying actor, as its
+            // failing constructor aborts it like a call.
+            let expected_revert = match self.machine().expected_revert {
+                Some(ref expected) if method == DEPLOY_NUM && expected.actor == from => {
+                    self.machine_mut().expected_revert.take()
+                }
+                _ => None,
+            };
+            let cheatcode_result = self.handle_cheatcode::<K>(method, from, params.clone())?;
+
+            let result = self
+                .0
+                .send::<KytheraKernel<K>>(from, to, method, params, value, gas_limit, read_only);
 
-            self.0
-                .send::<KytheraKernel<K>>(from, to, method, params, value, gas_limit, read_only)
+            let result = match cheatcode_result {
+                Some(cheatcode_result) => result.map(|_| cheatcode_result),
+                None => result,
+            };
+            match expected_revert {
+                Some(expected) => self.check_expected_revert(expected, to, method, result),
+                None => result,
+            }
         }
         // If any other actor, check if override caller
         else {
-            let caller = self.machine().override_context().caller.unwrap_or(from);
-            self.machine_mut().override_context.caller = None;
-            self.0
-                .send::<KytheraKernel<K>>(caller, to, method, params, value, gas_limit, read_only)
+            // Only the calls of the actor that armed the prank are pranked.
+            let prank = match self.machine().override_context.prank {
+                Some(ref prank) if prank.actor == from && prank.persistent => Some(prank.clone()),
+                Some(ref prank) if prank.actor == from => {
+                    self.machine_mut().override_context.prank.take()
+                }
+                _ => None,
+            };
+            let caller = prank.as_ref().map_or(from, |prank| prank.caller);
+            // The origin of the pranked call is restored once it returns.
+            let previous_origin = prank
+                .and_then(|prank| prank.origin)
+                .map(|origin| self.machine_mut().override_context.origin.replace(origin));
+
+            // Only the next call of the actor that armed the expected revert is checked.
+            let expected_revert = match self.machine().expected_revert {
+                Some(ref expected) if expected.actor == from => {
+                    self.machine_mut().expected_revert.take()
+                }
+                _ => None,
+            };
+            let expected_emit = match self.machine().expected_emit {
+                Some(ref expected) if expected.actor == from => {
+                    self.machine_mut().expected_emit.take()
+                }
+                _ => None,
+            };
+            let first_event = self.machine().emitted_events.len();
+
+            // Mocked calls are answered without running the callee.
+            let result = match self.mocked_call(&to, method, params.as_ref())? {
+                Some(mocked) => Ok(mocked),
+                None => self.0.send::<KytheraKernel<K>>(
+                    caller,
+                    to,
+                    method,
+                    params.clone(),
+                    value,
+                    gas_limit,
+                    read_only,
+                ),
+            };
+            if matches!(result, Ok(ref ret) if ret.exit_code.is_success()) {
+                self.record_expected_calls(&to, method, params.as_ref(), value)?;
+            }
+
+            if let Some(previous_origin) = previous_origin {
+                self.machine_mut().override_context.origin = previous_origin;
+            }
+
+            let result = match expected_emit {
+                Some(expected) => {
+                    self.check_expected_emit(expected, to, method, first_event, result)
+                }
+                None => result,
+            };
+            let result = match expected_revert {
+                Some(expected) => self.check_expected_revert(expected, to, method, result),
+                None => result,
+            };
+            // The gas limit is checked when the call to the test actor returns.
+            if from == self.origin() {
+                self.check_gas_limit(result)
+            } else {
+                result
+            }
         }
     }
 
@@ -288,20 +1385,93 @@ where
         &mut self,
         f: impl FnOnce(&mut Self) -> fvm::kernel::Result<InvocationResult>,
     ) -> fvm::kernel::Result<InvocationResult> {
+        let first_event = self.machine().emitted_events.len();
+        let expected_calls: Vec<u64> = self
+            .machine()
+            .expected_calls
+            .iter()
+            .map(|expected| expected.calls)
+            .collect();
+        let circ_supply_delta = self.machine().circ_supply_delta.clone();
+        let expected_proof_verifications = self.machine().expected_proof_verifications.clone();
+
         // This transmute is _safe_ because this type is "repr transparent".
         let inner_ptr = &mut self.0 as *mut C;
-        self.0.with_transaction(|inner: &mut C| unsafe {
+        let result = self.0.with_transaction(|inner: &mut C| unsafe {
             // Make sure that we've got the right pointer. Otherwise, this cast definitely isn't
             // safe.
             assert_eq!(inner_ptr, inner as *mut C);
 
             // Ok, we got the pointer we expected, casting back to the interceptor is safe.
             f(&mut *(inner as *mut C as *mut Self))
-        })
+        });
+
+        // Events emitted in a reverted transaction are discarded, as done by the inner call
+        // manager, and so are the expected calls they sent, the balances they dealt and the proof
+        // verifications they consumed.
+        if !matches!(result, Ok(ref ret) if ret.exit_code.is_success()) {
+            self.machine_mut().emitted_events.truncate(first_event);
+            self.machine_mut().circ_supply_delta = circ_supply_delta;
+            self.machine_mut().expected_proof_verifications = expected_proof_verifications;
+            for (expected, calls) in self
+                .machine_mut()
+                .expected_calls
+                .iter_mut()
+                .zip(expected_calls)
+            {
+                expected.calls = calls;
+            }
+        }
+
+        // The state changes of an expected revert have been reverted, the caller should now see
+        // a successful call.
+        if std::mem::take(&mut self.machine_mut().revert_caught) {
+            return result.map(|ret| InvocationResult {
+                exit_code: ExitCode::OK,
+                value: ret.value,
+            });
+        }
+        result
     }
 
     fn finish(self) -> (fvm::kernel::Result<FinishRet>, Self::Machine) {
-        self.0.finish()
+        let (result, mut machine) = self.0.finish();
+
+        let expected_revert = machine.expected_revert.take();
+        let caught_reverts = std::mem::take(&mut machine.caught_reverts);
+        let expected_emit = machine.expected_emit.take();
+        machine.emitted_events.clear();
+        machine.snapshots.clear();
+        machine.mocked_calls.clear();
+        machine.mocked_consensus_faults.clear();
+        let expected_calls = std::mem::take(&mut machine.expected_calls);
+        machine.gas_limit = None;
+        machine.read_only = false;
+        machine.mock_proofs = false;
+        machine.mock_signatures = None;
+        let expected_proof_verifications =
+            std::mem::take(machine.expected_proof_verifications.get_mut());
+        let expectation_failure = machine.expectation_failure.take();
+        let result = result.and_then(|ret| {
+            let failure = expectation_failure.or_else(|| {
+                check_finished_expectations(
+                    &ret,
+                    expected_revert,
+                    caught_reverts,
+                    expected_emit,
+                    &expected_calls,
+                    &expected_proof_verifications,
+                )
+            });
+            match failure {
+                Some(failure) => Err(ExecutionError::Fatal(anyhow::Error::new(
+                    ExpectationFailure(failure),
+                ))),
+                None => Ok(ret),
+            }
+        });
+
+        (result, machine)
     }
 
     fn machine(&self) -> &Self::Machine {
@@ -379,6 +1549,120 @@ where
     }
 
     fn append_event(&mut self, evt: StampedEvent) {
+        // Keep track of the events to check them against the ones expected by `ExpectEmit`.
+        self.machine_mut().emitted_events.push(evt.clone());
         self.0.append_event(evt)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_matches_state_shape() {
+        let current = Ipld::List(vec![Ipld::String(String::from("Basic Target Actor"))]);
+
+        // Same shape, different values.
+        assert!(matches_state_shape(
+            &current,
+            &Ipld::List(vec![Ipld::String(String::from("I am new value"))])
+        ));
+        // Optional fields can be set or not.
+        assert!(matches_state_shape(&current, &Ipld::List(vec![Ipld::Null])));
+
+        // Other kinds of values, list lengths or map keys make `Alter` warn.
+        assert!(!matches_state_shape(
+            &current,
+            &Ipld::List(vec![Ipld::Integer(1)])
+        ));
+        assert!(!matches_state_shape(
+            &current,
+            &Ipld::List(vec![Ipld::Integer(1), Ipld::Integer(2)])
+        ));
+        assert!(!matches_state_shape(&current, &Ipld::Integer(1)));
+        assert!(!matches_state_shape(
+            &Ipld::Map(BTreeMap::from([(String::from("a"), Ipld::Integer(1))])),
+            &Ipld::Map(BTreeMap::from([(String::from("b"), Ipld::Integer(1))]))
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_prank_params() {
+        let block = |params: Vec<u8>| Some(Block::new(DAG_CBOR, params));
+        let (caller, origin) = (Address::new_id(101), Address::new_id(102));
+
+        assert_eq!(
+            deserialize_prank_params("Prank", block(to_vec(&caller).unwrap())).unwrap(),
+            (101, None)
+        );
+        assert_eq!(
+            deserialize_prank_params("Prank", block(to_vec(&(caller, Some(origin))).unwrap()))
+                .unwrap(),
+            (101, Some(102))
+        );
+
+        // The decoding error of the shape sent is reported.
+        let params = to_vec(&(caller, "origin")).unwrap();
+        let expected = from_slice::<(Address, Option<Address>)>(&params).unwrap_err();
+        let err = deserialize_prank_params("Prank", block(params)).unwrap_err();
+        assert!(err.to_string().contains(&expected.to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_new_account_params() {
+        let block = |params: Vec<u8>| Some(Block::new(DAG_CBOR, params));
+
+        assert_eq!(
+            deserialize_new_account_params(block(to_vec("alice").unwrap())).unwrap(),
+            (String::from("alice"), None, KeyType::Secp256k1)
+        );
+        assert_eq!(
+            deserialize_new_account_params(block(
+                to_vec(&("bob", Some(TokenAmount::from_atto(100)), "Bls")).unwrap()
+            ))
+            .unwrap(),
+            (
+                String::from("bob"),
+                Some(TokenAmount::from_atto(100)),
+                KeyType::Bls
+            )
+        );
+
+        // The decoding error of the shape sent is reported.
+        let params = to_vec(&("bob", "balance", "Bls")).unwrap();
+        let expected = from_slice::<(String, Option<TokenAmount>, String)>(&params).unwrap_err();
+        let err = deserialize_new_account_params(block(params)).unwrap_err();
+        assert!(err.to_string().contains(&expected.to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_randomness_params() {
+        let block = |params: Vec<u8>| Some(Block::new(DAG_CBOR, params));
+        let randomness = RawBytes::new(vec![1; RANDOMNESS_LENGTH]);
+
+        let entries = deserialize_randomness_params(
+            "SetChainRandomness",
+            block(to_vec(&randomness).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].dst, entries[0].epoch), (None, None));
+
+        let entries = deserialize_randomness_params(
+            "SetChainRandomness",
+            block(to_vec(&vec![(Some(1i64), Some(2i64), randomness)]).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].dst, entries[0].epoch), (Some(1), Some(2)));
+
+        // The decoding error of the shape sent is reported.
+        let params = to_vec(&vec![(Some(1i64), "epoch", RawBytes::default())]).unwrap();
+        let expected =
+            from_slice::<Vec<(Option<i64>, Option<ChainEpoch>, RawBytes)>>(&params).unwrap_err();
+        let err = deserialize_randomness_params("SetChainRandomness", block(params)).unwrap_err();
+        assert!(err.to_string().contains(&expected.to_string()));
+    }
+}
@@ -1,8 +1,11 @@
+use cid::Cid;
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::randomness::RANDOMNESS_LENGTH;
 use fvm_shared::sys::out::network::NetworkContext;
 use fvm_shared::sys::out::vm::MessageContext;
 use fvm_shared::sys::TokenAmount;
 use fvm_shared::ActorID;
+use std::collections::HashMap;
 
 pub trait Override<S> {
     fn override_with_context(&self, context: &S) -> Self;
@@ -25,8 +28,62 @@ pub struct OverrideContext {
     /// The current call's origin actor ID.
     pub origin: Option<ActorID>,
 
-    /// The caller's actor ID.
-    pub caller: Option<ActorID>,
+    /// The prank overriding the caller of the calls sent by an actor.
+    pub prank: Option<Prank>,
+
+    /// The chain randomness returned to actors, falling back to the externs if no entry matches.
+    pub chain_randomness: Vec<RandomnessEntry>,
+
+    /// The beacon randomness returned to actors, falling back to the externs if no entry matches.
+    pub beacon_randomness: Vec<RandomnessEntry>,
+
+    /// The tipset CIDs returned to actors by epoch, falling back to the externs if not set.
+    pub tipset_cids: HashMap<ChainEpoch, Cid>,
+}
+
+/// Caller override set by the `Prank` and `StartPrank` cheatcodes.
+#[derive(Debug, Clone)]
+pub struct Prank {
+    /// The actor whose calls are pranked.
+    pub actor: ActorID,
+
+    /// The caller's actor ID seen by the pranked calls.
+    pub caller: ActorID,
+
+    /// The origin actor ID seen by the pranked calls and their sub-calls, if overridden.
+    pub origin: Option<ActorID>,
+
+    /// Whether the prank applies to every call until `StopPrank`, or only to the next one.
+    pub persistent: bool,
+}
+
+/// Randomness set by the `SetChainRandomness` and `SetBeaconRandomness` cheatcodes.
+#[derive(Debug, Clone)]
+pub struct RandomnessEntry {
+    /// The domain separation tag the randomness is returned for, any tag if none.
+    pub dst: Option<i64>,
+
+    /// The epoch the randomness is returned for, any epoch if none.
+    pub epoch: Option<ChainEpoch>,
+
+    /// The randomness returned.
+    pub randomness: [u8; RANDOMNESS_LENGTH],
+}
+
+/// Get the randomness of the most specific entry matching a domain separation tag and epoch.
+pub fn lookup_randomness(
+    entries: &[RandomnessEntry],
+    dst: i64,
+    epoch: ChainEpoch,
+) -> Option<[u8; RANDOMNESS_LENGTH]> {
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.dst.is_none_or(|entry_dst| entry_dst == dst)
+                && entry.epoch.is_none_or(|entry_epoch| entry_epoch == epoch)
+        })
+        .max_by_key(|entry| (entry.dst.is_some(), entry.epoch.is_some()))
+        .map(|entry| entry.randomness)
 }
 
 impl Override<OverrideContext> for NetworkContext {
@@ -1,13 +1,16 @@
 // Copyright 2023 Polyphene.
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use crate::call_manager::KytheraCallManager;
 use crate::externs::FakeExterns;
+use crate::keys::Keyring;
 use crate::machine::KytheraMachine;
+use crate::registry::ActorRegistry;
 use cid::Cid;
-use fvm::engine::EnginePool;
+use fvm::engine::{EngineConfig, EnginePool};
 use fvm::executor::DefaultExecutor;
+use fvm::DefaultKernel;
 
-use crate::kernel::KytheraKernel;
 use crate::utils::KYTHERA_NETWORK_ID;
 pub use fvm::executor::Executor as _;
 pub use fvm::executor::{ApplyFailure, ApplyKind, ApplyRet};
@@ -19,34 +22,84 @@ use fvm_shared::chainid::ChainID;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::message::Message;
 use fvm_shared::version::NetworkVersion;
-use fvm_shared::MethodNum;
+use fvm_shared::{ActorID, MethodNum};
+use std::collections::BTreeMap;
 
 const NETWORK_VERSION: NetworkVersion = NetworkVersion::V18;
 const DEFAULT_BASE_FEE: u64 = 100;
+const DEFAULT_GAS_LIMIT: u64 = 1000000000;
+
+/// Gas settings of the messages executed by a [`KytheraExecutor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasConfig {
+    /// Gas limit of each message, set on purpose by the CLI, the configuration or a Rust test,
+    /// the default one being used if `None`.
+    pub gas_limit: Option<u64>,
+    /// Maximum price per unit of gas paid by the sender of the messages.
+    pub gas_fee_cap: TokenAmount,
+    /// Price per unit of gas paid on top of the base fee.
+    pub gas_premium: TokenAmount,
+    /// Base fee of the network.
+    pub base_fee: TokenAmount,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            gas_limit: None,
+            gas_fee_cap: TokenAmount::default(),
+            gas_premium: TokenAmount::default(),
+            base_fee: TokenAmount::from_atto(DEFAULT_BASE_FEE),
+        }
+    }
+}
 
 /// Wrapper around `fvm` Executor with sane defaults.
 pub struct KytheraExecutor {
-    inner: DefaultExecutor<KytheraKernel>,
+    // The executor kernel is the default one so that its call manager is the
+    // `KytheraCallManager`: the message then goes through `KytheraCallManager::send` and
+    // `KytheraCallManager::finish`, which check the cheatcode expectations once it is applied.
+    // `KytheraCallManager::send` wraps the kernel of every invoked actor, the first one
+    // included, in a `KytheraKernel`, so actors still run with the Kythera syscalls.
+    inner: DefaultExecutor<DefaultKernel<KytheraCallManager>>,
     account_address: Address,
     target_actor_id: RawBytes,
+    gas_config: GasConfig,
+}
+
+/// Build the [`NetworkConfig`] used by Kythera machines.
+fn network_config(builtin_actors: Cid) -> NetworkConfig {
+    let mut nc = NetworkConfig::new(NETWORK_VERSION);
+    nc.override_actors(builtin_actors);
+    nc.enable_actor_debugging();
+    // If chain Id is 0 (invalid value) we set our default
+    if nc.chain_id == ChainID::from(0) {
+        nc.chain_id = ChainID::from(KYTHERA_NETWORK_ID)
+    }
+    nc
 }
 
 impl KytheraExecutor {
+    /// Create a new [`EnginePool`] to be shared between `Executor`s, so that actors code is only
+    /// compiled once. `concurrency` is the number of `Executor`s able to run messages at the
+    /// same time.
+    pub fn new_engine_pool(builtin_actors: Cid, concurrency: u32) -> EnginePool {
+        let mut engine_config: EngineConfig = (&network_config(builtin_actors)).into();
+        engine_config.concurrency = concurrency;
+        EnginePool::new_default(engine_config).expect("Should be able to start EnginePool")
+    }
+
     /// Create a new `Executor`.
     pub fn new(
+        engine: EnginePool,
         blockstore: MemoryBlockstore,
         state_root: Cid,
         builtin_actors: Cid,
         account_address: Address,
         target_actor_id: RawBytes,
+        externs: FakeExterns,
     ) -> Self {
-        let mut nc = NetworkConfig::new(NETWORK_VERSION);
-        nc.override_actors(builtin_actors);
-        nc.enable_actor_debugging();
-        // If chain Id is 0 (invalid value) we set our default
-        if nc.chain_id == ChainID::from(0) {
-            nc.chain_id = ChainID::from(KYTHERA_NETWORK_ID)
-        }
+        let nc = network_config(builtin_actors);
 
         let mut mc = nc.for_epoch(0, 0, state_root);
         mc.set_base_fee(TokenAmount::from_atto(DEFAULT_BASE_FEE))
@@ -54,17 +107,13 @@ impl KytheraExecutor {
 
         let code_cids = vec![];
 
-        let engine = EnginePool::new_default((&mc.network.clone()).into())
-            .expect("Should be able to start EnginePool");
         engine
             .acquire()
             .preload(&blockstore, &code_cids)
             .expect("Should be able to preload Executor");
 
         let machine = KytheraMachine::<DefaultMachine<MemoryBlockstore, FakeExterns>>::new(
-            mc,
-            blockstore,
-            FakeExterns::new(),
+            mc, blockstore, externs,
         )
         .expect("Should be able to start KytheraMachine");
 
@@ -72,6 +121,7 @@ impl KytheraExecutor {
             inner: DefaultExecutor::new(engine, machine).expect("Should be able to start Executor"),
             account_address,
             target_actor_id,
+            gas_config: GasConfig::default(),
         }
     }
 
@@ -81,24 +131,133 @@ impl KytheraExecutor {
         to: Address,
         method_num: MethodNum,
         sequence: u64,
+    ) -> Result<ApplyRet, anyhow::Error> {
+        let params = self.target_actor_id.clone();
+        self.execute_method_with_params(to, method_num, params, sequence)
+    }
+
+    /// Execute the provided method with the given parameters.
+    pub fn execute_method_with_params(
+        &mut self,
+        to: Address,
+        method_num: MethodNum,
+        params: RawBytes,
+        sequence: u64,
+    ) -> Result<ApplyRet, anyhow::Error> {
+        self.execute_method_with_value(to, method_num, params, TokenAmount::default(), sequence)
+    }
+
+    /// Execute the provided method with the given parameters, sending the given value.
+    pub fn execute_method_with_value(
+        &mut self,
+        to: Address,
+        method_num: MethodNum,
+        params: RawBytes,
+        value: TokenAmount,
+        sequence: u64,
+    ) -> Result<ApplyRet, anyhow::Error> {
+        self.execute(to, method_num, params, value, sequence, false)
+    }
+
+    /// Execute the provided method with the given parameters as a read-only call, in which the
+    /// actors can neither change their state, transfer value nor create actors.
+    pub fn execute_method_read_only(
+        &mut self,
+        to: Address,
+        method_num: MethodNum,
+        params: RawBytes,
+        sequence: u64,
+    ) -> Result<ApplyRet, anyhow::Error> {
+        self.execute(
+            to,
+            method_num,
+            params,
+            TokenAmount::default(),
+            sequence,
+            true,
+        )
+    }
+
+    /// Execute a message calling the provided method, as a read-only call or not.
+    fn execute(
+        &mut self,
+        to: Address,
+        method_num: MethodNum,
+        params: RawBytes,
+        value: TokenAmount,
+        sequence: u64,
+        read_only: bool,
     ) -> Result<ApplyRet, anyhow::Error> {
         let message = Message {
             from: self.account_address,
             to,
-            gas_limit: 1000000000,
+            gas_limit: self.gas_config.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
             method_num,
-            params: self.target_actor_id.clone(),
+            params,
             sequence,
             version: 0,
-            value: TokenAmount::default(),
-            gas_fee_cap: TokenAmount::default(),
-            gas_premium: TokenAmount::default(),
+            value,
+            gas_fee_cap: self.gas_config.gas_fee_cap.clone(),
+            gas_premium: self.gas_config.gas_premium.clone(),
         };
 
+        self.inner.gas_limited = false;
+        self.inner.read_only = read_only;
         self.inner
             .execute_message(message, ApplyKind::Explicit, 100)
     }
 
+    /// Whether the gas of the last executed message was limited on purpose, by the `GasLimit`
+    /// cheatcode or by the gas limit of the [`GasConfig`].
+    pub fn gas_limited(&self) -> bool {
+        self.inner.gas_limited || self.gas_config.gas_limit.is_some()
+    }
+
+    /// Set the keys and labels of the test accounts available to the cheatcodes.
+    pub fn with_keyring(mut self, keyring: Keyring) -> Self {
+        self.inner.keyring = keyring;
+        self
+    }
+
+    /// Get the keys and labels of the test accounts, including the accounts created by the
+    /// cheatcodes of the executed messages.
+    pub fn keyring(&self) -> &Keyring {
+        self.inner.keyring()
+    }
+
+    /// Set the tokens added to the circulating supply by the cheatcodes of the previous messages.
+    pub fn with_circ_supply_delta(mut self, circ_supply_delta: TokenAmount) -> Self {
+        self.inner.circ_supply_delta = circ_supply_delta;
+        self
+    }
+
+    /// Get the tokens added to the circulating supply by the cheatcodes setting balances,
+    /// including the ones of the executed messages.
+    pub fn circ_supply_delta(&self) -> &TokenAmount {
+        &self.inner.circ_supply_delta
+    }
+
+    /// Set the actors that can be deployed by the `Deploy` cheatcode.
+    pub fn with_registry(mut self, registry: ActorRegistry) -> Self {
+        self.inner.registry = registry;
+        self
+    }
+
+    /// Set the IDs of the actors deployed along with the target actor, by name, returned by the
+    /// `Fixture` cheatcode.
+    pub fn with_fixtures(mut self, fixtures: BTreeMap<String, ActorID>) -> Self {
+        self.inner.fixtures = fixtures;
+        self
+    }
+
+    /// Set the gas limit, fee cap and premium of the executed messages, and the base fee of the
+    /// network.
+    pub fn with_gas_config(mut self, gas_config: GasConfig) -> Self {
+        self.inner.set_base_fee(gas_config.base_fee.clone());
+        self.gas_config = gas_config;
+        self
+    }
+
     /// Convert the executor back into a [`Blockstore`].
     pub fn into_store(mut self) -> (Cid, MemoryBlockstore) {
         let root = self
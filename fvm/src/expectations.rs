@@ -0,0 +1,136 @@
+use crate::mocks::ProofKind;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::event::ActorEvent;
+use fvm_shared::{ActorID, MethodNum};
+use std::fmt;
+
+/// Revert expected on the next call sent by an actor, armed by the `ExpectRevert` cheatcode.
+#[derive(Debug, Clone)]
+pub struct ExpectedRevert {
+    /// The actor whose next call is expected to revert.
+    pub actor: ActorID,
+
+    /// The exit code the call is expected to abort with.
+    pub exit_code: ExitCode,
+
+    /// A message the abort message of the call is expected to contain.
+    pub message: Option<String>,
+}
+
+/// Revert that happened as expected, whose abort message still has to be checked once the
+/// message backtrace is available.
+#[derive(Debug, Clone)]
+pub struct CaughtRevert {
+    /// The actor that aborted.
+    pub source: ActorID,
+
+    /// The method that was invoked.
+    pub method: MethodNum,
+
+    /// The exit code the actor aborted with.
+    pub exit_code: ExitCode,
+
+    /// The message the abort message is expected to contain.
+    pub message: String,
+}
+
+/// Events expected to be emitted during the next call sent by an actor, armed by the
+/// `ExpectEmit` cheatcode.
+#[derive(Debug, Clone)]
+pub struct ExpectedEmit {
+    /// The actor whose next call is expected to emit the events.
+    pub actor: ActorID,
+
+    /// The events expected to be emitted, in order.
+    pub events: Vec<ActorEvent>,
+}
+
+/// Calls expected to be sent during the rest of the message, armed by the `ExpectCall` cheatcode.
+#[derive(Debug, Clone)]
+pub struct ExpectedCall {
+    /// The address the calls are expected to be sent to.
+    pub to: Address,
+
+    /// The actor ID of the address, if it is assigned to an actor.
+    pub to_id: Option<ActorID>,
+
+    /// The method the calls are expected to invoke.
+    pub method: MethodNum,
+
+    /// The parameters the calls are expected to be sent with, any parameters match if none.
+    pub params: Option<RawBytes>,
+
+    /// The value the calls are expected to transfer, any value matches if none.
+    pub value: Option<TokenAmount>,
+
+    /// The number of calls expected.
+    pub count: u64,
+
+    /// The number of matching calls sent so far.
+    pub calls: u64,
+}
+
+impl ExpectedCall {
+    /// Check whether a call matches the expected one.
+    pub fn matches(
+        &self,
+        to: &Address,
+        to_id: Option<ActorID>,
+        method: MethodNum,
+        params: &[u8],
+        value: &TokenAmount,
+    ) -> bool {
+        (&self.to == to || (self.to_id.is_some() && self.to_id == to_id))
+            && self.method == method
+            && self
+                .params
+                .as_ref()
+                .is_none_or(|expected| expected.bytes() == params)
+            && self.value.as_ref().is_none_or(|expected| expected == value)
+    }
+}
+
+/// Format an event as its list of entries, with values hex encoded.
+pub fn format_event(event: &ActorEvent) -> String {
+    let entries = event
+        .entries
+        .iter()
+        .map(|entry| {
+            let value = entry
+                .value
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>();
+            format!("{}: 0x{} (flags {})", entry.key, value, entry.flags.bits())
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("[{entries}]")
+}
+
+/// Proof verification expected during the rest of the message, armed by the `ExpectVerifyProof`
+/// cheatcode.
+#[derive(Debug, Clone)]
+pub struct ExpectedProofVerification {
+    /// The kind of proof expected to be verified.
+    pub kind: ProofKind,
+
+    /// The verdict returned for the proof.
+    pub verdict: bool,
+}
+
+/// Error of a message during which an expectation set by a cheatcode was not met. It fails the
+/// message whatever the type of the test method, unlike the other errors of the cheatcodes.
+#[derive(Debug, Clone)]
+pub struct ExpectationFailure(pub String);
+
+impl fmt::Display for ExpectationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ExpectationFailure {}
@@ -9,37 +9,51 @@ use fvm_shared::clock::ChainEpoch;
 use fvm_shared::consensus;
 use fvm_shared::IDENTITY_HASH;
 use multihash::MultihashGeneric;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Provides chain or beacon randomness externally.
-pub type RandomnessSource = Rc<dyn Fn(i64, ChainEpoch, &[u8]) -> Result<[u8; 32]>>;
+pub type RandomnessSource = Arc<dyn Fn(i64, ChainEpoch, &[u8]) -> Result<[u8; 32]> + Send + Sync>;
 
 /// Returns a randomness source that returns a constant value.
 pub fn const_randomness(v: [u8; 32]) -> RandomnessSource {
-    Rc::new(move |_pers, _round, _entropy| Ok(v))
+    Arc::new(move |_pers, _round, _entropy| Ok(v))
 }
 
 /// Provides consensus fault evaluation externally.
-pub type ConsensusFaultSource =
-    Rc<dyn Fn(&[u8], &[u8], &[u8]) -> Result<(Option<consensus::ConsensusFault>, i64)>>;
+pub type ConsensusFaultSource = Arc<
+    dyn Fn(&[u8], &[u8], &[u8]) -> Result<(Option<consensus::ConsensusFault>, i64)> + Send + Sync,
+>;
 
 /// Returns a constant evaluation of consensus fault evidence.
 pub fn const_consensus_fault(
     fault: Option<consensus::ConsensusFault>,
     epoch: ChainEpoch,
 ) -> ConsensusFaultSource {
-    Rc::new(move |_h1, _h2, _extra| Ok((fault.clone(), epoch)))
+    Arc::new(move |_h1, _h2, _extra| Ok((fault.clone(), epoch)))
 }
 
 /// Provides tipset CIDs externally.
-pub type TipsetSource = Rc<dyn Fn(ChainEpoch) -> Result<Cid>>;
+pub type TipsetSource = Arc<dyn Fn(ChainEpoch) -> Result<Cid> + Send + Sync>;
 
 /// Returns a tipset source that returns a constant value.
 pub fn const_tipset(cid: Cid) -> TipsetSource {
-    Rc::new(move |_epoch| Ok(cid))
+    Arc::new(move |_epoch| Ok(cid))
+}
+
+/// Returns a tipset source that returns an identity-hash CID of the epoch.
+pub fn epoch_tipset() -> TipsetSource {
+    Arc::new(|epoch| {
+        Ok(Cid::new_v1(
+            DAG_CBOR,
+            MultihashGeneric::wrap(IDENTITY_HASH, &epoch.to_be_bytes())?,
+        ))
+    })
 }
 
 /// An implementation of VM externs that can be controlled externally for tests.
+///
+/// The sources are `Arc`s of `Send + Sync` closures as the externs of the `Tester` are shared by
+/// the threads running its tests, each cloning them into its own Machine.
 #[derive(Clone)]
 pub struct FakeExterns {
     chain_randomness: RandomnessSource,
@@ -49,16 +63,14 @@ pub struct FakeExterns {
 }
 
 impl FakeExterns {
-    /// Returns a new fake externs that returns constant zero values for all calls.
+    /// Returns a new fake externs that returns constant zero values for all calls, and an
+    /// identity-hash CID of the epoch for tipsets.
     pub fn new() -> Self {
         Self {
             chain_randomness: const_randomness([0; 32]),
             beacon_randomness: const_randomness([0; 32]),
             consensus_fault: const_consensus_fault(None, 0),
-            tipset: const_tipset(Cid::new_v1(
-                DAG_CBOR,
-                MultihashGeneric::wrap(IDENTITY_HASH, &0u64.to_be_bytes()).unwrap(),
-            )),
+            tipset: epoch_tipset(),
         }
     }
 
@@ -81,11 +93,8 @@ impl FakeExterns {
 }
 
 impl Chain for FakeExterns {
-    fn get_tipset_cid(&self, epoch: fvm_shared::clock::ChainEpoch) -> Result<Cid> {
-        Ok(Cid::new_v1(
-            DAG_CBOR,
-            MultihashGeneric::wrap(IDENTITY_HASH, &epoch.to_be_bytes()).unwrap(),
-        ))
+    fn get_tipset_cid(&self, epoch: ChainEpoch) -> Result<Cid> {
+        (self.tipset)(epoch)
     }
 }
 
@@ -1,16 +1,17 @@
 use crate::call_manager::KytheraCallManager;
-use crate::context::Override;
+use crate::context::{lookup_randomness, Override};
 use crate::machine::KytheraMachine;
+use crate::mocks::ProofKind;
 use cid::Cid;
 use fvm::call_manager::CallManager;
 use fvm::gas::{Gas, GasTimer, PriceList};
 use fvm::kernel::{
     ActorOps, BlockId, BlockRegistry, BlockStat, CircSupplyOps, CryptoOps, DebugOps, EventOps,
-    GasOps, IpldBlockOps, LimiterOps, MessageOps, NetworkOps, RandomnessOps, SelfOps, SendOps,
-    SendResult,
+    ExecutionError, GasOps, IpldBlockOps, LimiterOps, MessageOps, NetworkOps, RandomnessOps,
+    SelfOps, SendOps, SendResult,
 };
 use fvm::machine::Machine;
-use fvm::{DefaultKernel, Kernel};
+use fvm::{syscall_error, DefaultKernel, Kernel};
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::consensus::ConsensusFault;
@@ -33,6 +34,41 @@ pub struct KytheraKernel<K = DefaultKernel<KytheraCallManager>> {
     inner: K,
 }
 
+impl<M, C, K> KytheraKernel<K>
+where
+    M: Machine,
+    C: CallManager<Machine = KytheraMachine<M>>,
+    K: Kernel<CallManager = KytheraCallManager<C>>,
+{
+    /// Get the verdict of a mocked proof verification, consuming the first expected verification
+    /// of its kind. Returns none if the proof should be verified.
+    fn mocked_proof_verdict(&self, kind: ProofKind) -> Option<bool> {
+        let machine = self.machine();
+        let mut expected = machine.expected_proof_verifications.borrow_mut();
+        match expected.iter().position(|expected| expected.kind == kind) {
+            Some(index) => Some(expected.remove(index).verdict),
+            None => machine.mock_proofs.then_some(true),
+        }
+    }
+
+    /// Check that randomness can be drawn for an epoch, which can neither be negative nor after
+    /// the current epoch, possibly set by the `Epoch` cheatcode.
+    fn check_randomness_epoch(&self, rand_epoch: ChainEpoch) -> fvm::kernel::Result<()> {
+        if rand_epoch < 0 {
+            return Err(syscall_error!(IllegalArgument; "randomness epoch is negative").into());
+        }
+        if rand_epoch > self.machine().context().epoch {
+            return Err(syscall_error!(
+                IllegalArgument;
+                "cannot draw randomness from the future epoch {}",
+                rand_epoch
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
 impl<M, C, K> Kernel for KytheraKernel<K>
 where
     M: Machine,
@@ -163,7 +199,7 @@ where
     K: Kernel<CallManager = KytheraCallManager<C>>,
 {
     fn total_fil_circ_supply(&self) -> fvm::kernel::Result<TokenAmount> {
-        self.inner.total_fil_circ_supply()
+        Ok(self.inner.total_fil_circ_supply()? + &self.machine().circ_supply_delta)
     }
 }
 
@@ -180,8 +216,20 @@ where
         signer: &Address,
         plaintext: &[u8],
     ) -> fvm::kernel::Result<bool> {
-        self.inner
-            .verify_signature(sig_type, signature, signer, plaintext)
+        match self.machine().mock_signatures {
+            Some(verdict) => {
+                let charge = self
+                    .inner
+                    .price_list()
+                    .on_verify_signature(sig_type, plaintext.len());
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(verdict)
+            }
+            None => self
+                .inner
+                .verify_signature(sig_type, signature, signer, plaintext),
+        }
     }
 
     fn recover_secp_public_key(
@@ -205,11 +253,27 @@ where
     }
 
     fn verify_seal(&self, vi: &SealVerifyInfo) -> fvm::kernel::Result<bool> {
-        self.inner.verify_seal(vi)
+        match self.mocked_proof_verdict(ProofKind::Seal) {
+            Some(verdict) => {
+                let charge = self.inner.price_list().on_verify_seal(vi);
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(verdict)
+            }
+            None => self.inner.verify_seal(vi),
+        }
     }
 
     fn verify_post(&self, verify_info: &WindowPoStVerifyInfo) -> fvm::kernel::Result<bool> {
-        self.inner.verify_post(verify_info)
+        match self.mocked_proof_verdict(ProofKind::Post) {
+            Some(verdict) => {
+                let charge = self.inner.price_list().on_verify_post(verify_info);
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(verdict)
+            }
+            None => self.inner.verify_post(verify_info),
+        }
     }
 
     fn verify_consensus_fault(
@@ -218,22 +282,82 @@ where
         h2: &[u8],
         extra: &[u8],
     ) -> fvm::kernel::Result<Option<ConsensusFault>> {
-        self.inner.verify_consensus_fault(h1, h2, extra)
+        let mocked = self
+            .machine()
+            .mocked_consensus_faults
+            .iter()
+            .find(|mocked| mocked.h1 == h1 && mocked.h2 == h2 && mocked.extra == extra);
+
+        match mocked {
+            Some(mocked) => {
+                let charge = self.inner.price_list().on_verify_consensus_fault(
+                    h1.len(),
+                    h2.len(),
+                    extra.len(),
+                );
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(mocked.fault.clone())
+            }
+            None => self.inner.verify_consensus_fault(h1, h2, extra),
+        }
     }
 
     fn batch_verify_seals(&self, vis: &[SealVerifyInfo]) -> fvm::kernel::Result<Vec<bool>> {
-        self.inner.batch_verify_seals(vis)
+        let machine = self.machine();
+        let mocked = machine.mock_proofs
+            || machine
+                .expected_proof_verifications
+                .borrow()
+                .iter()
+                .any(|expected| expected.kind == ProofKind::Seal);
+        if !mocked {
+            return self.inner.batch_verify_seals(vis);
+        }
+
+        // Each seal of the batch consumes an expected seal verification, and is charged as by the
+        // inner kernel.
+        vis.iter()
+            .map(|vi| match self.mocked_proof_verdict(ProofKind::Seal) {
+                Some(verdict) => {
+                    let charge = self.inner.price_list().on_verify_seal(vi);
+                    let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                    Ok(verdict)
+                }
+                None => self
+                    .inner
+                    .batch_verify_seals(std::slice::from_ref(vi))
+                    .map(|verdicts| verdicts.first().copied().unwrap_or(false)),
+            })
+            .collect()
     }
 
     fn verify_aggregate_seals(
         &self,
         aggregate: &AggregateSealVerifyProofAndInfos,
     ) -> fvm::kernel::Result<bool> {
-        self.inner.verify_aggregate_seals(aggregate)
+        match self.mocked_proof_verdict(ProofKind::AggregateSeals) {
+            Some(verdict) => {
+                let charge = self.inner.price_list().on_verify_aggregate_seals(aggregate);
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(verdict)
+            }
+            None => self.inner.verify_aggregate_seals(aggregate),
+        }
     }
 
     fn verify_replica_update(&self, replica: &ReplicaUpdateInfo) -> fvm::kernel::Result<bool> {
-        self.inner.verify_replica_update(replica)
+        match self.mocked_proof_verdict(ProofKind::ReplicaUpdate) {
+            Some(verdict) => {
+                let charge = self.inner.price_list().on_verify_replica_update(replica);
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(verdict)
+            }
+            None => self.inner.verify_replica_update(replica),
+        }
     }
 }
 
@@ -278,11 +402,22 @@ where
     }
 
     fn gas_available(&self) -> Gas {
-        self.inner.gas_available()
+        // The gas limit set by the `GasLimit` cheatcode bounds the execution of the actors.
+        let available = self.inner.gas_available();
+        match self.machine().gas_limit {
+            Some(gas_limit) => available.min(Gas::new(gas_limit) - self.inner.gas_used()),
+            None => available,
+        }
     }
 
     fn charge_gas(&self, name: &str, compute: Gas) -> fvm::kernel::Result<GasTimer> {
-        self.inner.charge_gas(name, compute)
+        let timer = self.inner.charge_gas(name, compute)?;
+        match self.machine().gas_limit {
+            Some(gas_limit) if self.inner.gas_used() > Gas::new(gas_limit) => {
+                Err(ExecutionError::OutOfGas)
+            }
+            _ => Ok(timer),
+        }
     }
 
     fn price_list(&self) -> &PriceList {
@@ -316,7 +451,15 @@ where
     }
 
     fn tipset_cid(&self, epoch: ChainEpoch) -> fvm::kernel::Result<Cid> {
-        self.inner.tipset_cid(epoch)
+        // The inner kernel checks the lookback and charges the gas of the syscall.
+        let cid = self.inner.tipset_cid(epoch)?;
+        Ok(self
+            .machine()
+            .override_context()
+            .tipset_cids
+            .get(&epoch)
+            .copied()
+            .unwrap_or(cid))
     }
 }
 
@@ -332,8 +475,22 @@ where
         rand_epoch: ChainEpoch,
         entropy: &[u8],
     ) -> fvm::kernel::Result<[u8; RANDOMNESS_LENGTH]> {
-        self.inner
-            .get_randomness_from_tickets(personalization, rand_epoch, entropy)
+        self.check_randomness_epoch(rand_epoch)?;
+        match lookup_randomness(
+            &self.machine().override_context().chain_randomness,
+            personalization,
+            rand_epoch,
+        ) {
+            Some(randomness) => {
+                let charge = self.inner.price_list().on_get_randomness(entropy.len());
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(randomness)
+            }
+            None => self
+                .inner
+                .get_randomness_from_tickets(personalization, rand_epoch, entropy),
+        }
     }
 
     fn get_randomness_from_beacon(
@@ -342,8 +499,22 @@ where
         rand_epoch: ChainEpoch,
         entropy: &[u8],
     ) -> fvm::kernel::Result<[u8; RANDOMNESS_LENGTH]> {
-        self.inner
-            .get_randomness_from_beacon(personalization, rand_epoch, entropy)
+        self.check_randomness_epoch(rand_epoch)?;
+        match lookup_randomness(
+            &self.machine().override_context().beacon_randomness,
+            personalization,
+            rand_epoch,
+        ) {
+            Some(randomness) => {
+                let charge = self.inner.price_list().on_get_randomness(entropy.len());
+                let _ = self.charge_gas(&charge.name, charge.total())?;
+
+                Ok(randomness)
+            }
+            None => self
+                .inner
+                .get_randomness_from_beacon(personalization, rand_epoch, entropy),
+        }
     }
 }
 
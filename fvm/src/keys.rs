@@ -0,0 +1,215 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use bls_signatures::Serialize as _;
+use cid::multihash::Code;
+use fvm::account_actor;
+use fvm::machine::Manifest;
+use fvm::state_tree::ActorState;
+use fvm::EMPTY_ARR_CID;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::crypto::signature::SECP_SIG_LEN;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::ActorID;
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+
+/// Seed of the key of the account sending the test messages.
+const DEFAULT_ACCOUNT_SEED: u64 = 8;
+
+/// ID of the Ethereum Address Manager actor, namespace of the delegated addresses.
+const EAM_ACTOR_ID: ActorID = 10;
+
+/// Type of the key of a test account.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    /// secp256k1 key of an `f1` account.
+    #[default]
+    Secp256k1,
+    /// BLS key of an `f3` account.
+    Bls,
+    /// secp256k1 key of an `f410` Ethereum account.
+    Delegated,
+}
+
+impl KeyType {
+    /// Get the key type from its name in the cheatcodes parameters.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Secp256k1" => Some(KeyType::Secp256k1),
+            "Bls" => Some(KeyType::Bls),
+            "Delegated" => Some(KeyType::Delegated),
+            _ => None,
+        }
+    }
+}
+
+/// Secret key of a test account.
+#[derive(Clone)]
+pub enum TestKey {
+    Secp256k1(SecretKey),
+    Bls(bls_signatures::PrivateKey),
+    Delegated(SecretKey),
+}
+
+impl TestKey {
+    /// Derive the key of a given type from a label.
+    pub fn from_label(label: &str, key_type: KeyType) -> Self {
+        let seed: [u8; 32] = blake2b_simd::Params::new()
+            .hash_length(32)
+            .hash(label.as_bytes())
+            .as_bytes()
+            .try_into()
+            .expect("Blake2b hash should be 32 bytes long");
+        let rng = &mut ChaCha8Rng::from_seed(seed);
+
+        match key_type {
+            KeyType::Secp256k1 => TestKey::Secp256k1(SecretKey::random(rng)),
+            KeyType::Bls => TestKey::Bls(bls_signatures::PrivateKey::generate(rng)),
+            KeyType::Delegated => TestKey::Delegated(SecretKey::random(rng)),
+        }
+    }
+
+    /// Get the type of the key.
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            TestKey::Secp256k1(_) => KeyType::Secp256k1,
+            TestKey::Bls(_) => KeyType::Bls,
+            TestKey::Delegated(_) => KeyType::Delegated,
+        }
+    }
+
+    /// Get the address of the account of the key.
+    pub fn address(&self) -> Address {
+        match self {
+            TestKey::Secp256k1(secret_key) => secp256k1_address(secret_key),
+            TestKey::Bls(private_key) => Address::new_bls(&private_key.public_key().as_bytes())
+                .expect("PublicKey length should be valid"),
+            TestKey::Delegated(secret_key) => {
+                let public_key = PublicKey::from_secret_key(secret_key).serialize();
+                let hash = Keccak256::digest(&public_key[1..]);
+                Address::new_delegated(EAM_ACTOR_ID, &hash[12..])
+                    .expect("Delegated address length should be valid")
+            }
+        }
+    }
+
+    /// Sign a message. secp256k1 keys sign a 32 bytes digest, returning `None` for other lengths,
+    /// and BLS keys sign the message itself.
+    pub fn sign(&self, message: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            TestKey::Secp256k1(secret_key) | TestKey::Delegated(secret_key) => {
+                let digest = <&[u8; 32]>::try_from(message).ok()?;
+                Some(sign(secret_key, digest).to_vec())
+            }
+            TestKey::Bls(private_key) => Some(private_key.sign(message).as_bytes()),
+        }
+    }
+}
+
+/// Build the state of the account actor of a key, putting its state in a blockstore. Delegated
+/// keys get an Ethereum account.
+pub fn account_actor_state<BS: Blockstore>(
+    key: &TestKey,
+    manifest: &Manifest,
+    blockstore: &BS,
+    balance: TokenAmount,
+) -> anyhow::Result<ActorState> {
+    let address = key.address();
+    let (code, state, delegated_address) = match key {
+        TestKey::Secp256k1(_) | TestKey::Bls(_) => (
+            *manifest.get_account_code(),
+            blockstore.put_cbor(&account_actor::State { address }, Code::Blake2b256)?,
+            None,
+        ),
+        TestKey::Delegated(_) => (
+            *manifest.get_ethaccount_code(),
+            *EMPTY_ARR_CID,
+            Some(address),
+        ),
+    };
+
+    Ok(ActorState::new(code, state, balance, 0, delegated_address))
+}
+
+/// Keys and labels of the test accounts, shared between the `Tester` and the cheatcodes.
+#[derive(Clone)]
+pub struct Keyring {
+    // Keys of the accounts by key address.
+    keys: HashMap<Address, TestKey>,
+    // Labels of the accounts by actor ID.
+    labels: HashMap<ActorID, String>,
+}
+
+impl Keyring {
+    /// Create a keyring holding the key of the account sending the test messages.
+    pub fn new() -> Self {
+        let default_key = default_secret_key();
+        Self {
+            keys: HashMap::from([(
+                secp256k1_address(&default_key),
+                TestKey::Secp256k1(default_key),
+            )]),
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Add a key, returning the address of its account.
+    pub fn add_key(&mut self, key: TestKey) -> Address {
+        let address = key.address();
+        self.keys.insert(address, key);
+        address
+    }
+
+    /// Get the key of an account by key address.
+    pub fn key(&self, address: &Address) -> Option<&TestKey> {
+        self.keys.get(address)
+    }
+
+    /// Set the label of an actor.
+    pub fn set_label(&mut self, id: ActorID, label: String) {
+        self.labels.insert(id, label);
+    }
+
+    /// Get the label of an actor.
+    pub fn label(&self, id: ActorID) -> Option<&str> {
+        self.labels.get(&id).map(String::as_str)
+    }
+
+    /// Get the labels of the actors by actor ID.
+    pub fn labels(&self) -> &HashMap<ActorID, String> {
+        &self.labels
+    }
+}
+
+impl Default for Keyring {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the secret key of the account sending the test messages.
+pub fn default_secret_key() -> SecretKey {
+    SecretKey::random(&mut ChaCha8Rng::seed_from_u64(DEFAULT_ACCOUNT_SEED))
+}
+
+/// Returns the secp256k1 address of a secret key.
+pub fn secp256k1_address(secret_key: &SecretKey) -> Address {
+    Address::new_secp256k1(&PublicKey::from_secret_key(secret_key).serialize())
+        .expect("PublicKey length should be valid")
+}
+
+/// Signs a 32 bytes digest, returning the signature followed by its recovery ID.
+pub fn sign(secret_key: &SecretKey, digest: &[u8; 32]) -> [u8; SECP_SIG_LEN] {
+    let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(digest), secret_key);
+
+    let mut bytes = [0u8; SECP_SIG_LEN];
+    bytes[..SECP_SIG_LEN - 1].copy_from_slice(&signature.serialize());
+    bytes[SECP_SIG_LEN - 1] = recovery_id.serialize();
+    bytes
+}
@@ -18,6 +18,11 @@ pub mod engine {
     pub use fvm::engine::EnginePool;
 }
 
+pub mod event {
+    pub use crate::expectations::format_event;
+    pub use fvm_shared::event::{ActorEvent, Entry, Flags, StampedEvent};
+}
+
 pub mod executor;
 pub mod state_tree {
     pub use fvm::state_tree::ActorState;
@@ -29,9 +34,16 @@ pub mod trace {
 }
 pub type Account = (ActorID, Address);
 
+pub use expectations::ExpectationFailure;
+
 mod call_manager;
 mod context;
+mod expectations;
 pub mod externs;
 mod kernel;
+pub mod keys;
 pub mod machine;
+mod mocks;
+pub mod registry;
+mod snapshot;
 pub(crate) mod utils;
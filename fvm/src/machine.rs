@@ -1,16 +1,73 @@
 use crate::context::OverrideContext;
+use crate::expectations::{
+    CaughtRevert, ExpectedCall, ExpectedEmit, ExpectedProofVerification, ExpectedRevert,
+};
 use crate::externs::FakeExterns;
+use crate::keys::Keyring;
+use crate::mocks::{MockedCall, MockedConsensusFault};
+use crate::registry::ActorRegistry;
+use crate::snapshot::StateSnapshot;
 use fvm::machine::MachineContext;
 pub use fvm::machine::{DefaultMachine, Machine, Manifest, NetworkConfig};
 use fvm::state_tree::StateTree;
 use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::event::StampedEvent;
+use fvm_shared::ActorID;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 
 pub struct KytheraMachine<M = DefaultMachine<MemoryBlockstore, FakeExterns>> {
     inner: M,
+    // Context of the inner machine, kept here so that its base fee can be set after creation.
+    context: MachineContext,
     // We store OverrideContext here as the Kernel is instantiated at every new implicit message
     // and the CallManager needs to have only one inner value for the unsafe code in `with_transaction()`
     // to work.
     pub(crate) override_context: OverrideContext,
+    // Revert expected on the next call of an actor.
+    pub(crate) expected_revert: Option<ExpectedRevert>,
+    // Whether the last call reverted as expected, so that its failure is not propagated.
+    pub(crate) revert_caught: bool,
+    // Reverts whose abort message is checked at the end of the message.
+    pub(crate) caught_reverts: Vec<CaughtRevert>,
+    // Events expected to be emitted during the next call of an actor.
+    pub(crate) expected_emit: Option<ExpectedEmit>,
+    // Events emitted during the message that have not been reverted.
+    pub(crate) emitted_events: Vec<StampedEvent>,
+    // States recorded during the message, indexed by their snapshot ID.
+    pub(crate) snapshots: Vec<StateSnapshot>,
+    // Calls answered without running the callee.
+    pub(crate) mocked_calls: Vec<MockedCall>,
+    // Consensus fault evidence answered without calling the externs.
+    pub(crate) mocked_consensus_faults: Vec<MockedConsensusFault>,
+    // Whether the proofs not expected to be verified are answered as valid without verifying them.
+    pub(crate) mock_proofs: bool,
+    // Proof verifications expected during the rest of the message, consumed by the kernel.
+    pub(crate) expected_proof_verifications: RefCell<Vec<ExpectedProofVerification>>,
+    // Calls expected to be sent during the rest of the message.
+    pub(crate) expected_calls: Vec<ExpectedCall>,
+    // Keys and labels of the test accounts, extended by the cheatcodes creating accounts.
+    pub(crate) keyring: Keyring,
+    // Verdict returned for signature verifications instead of verifying them, if set.
+    pub(crate) mock_signatures: Option<bool>,
+    // Actors that can be deployed by the `Deploy` cheatcode.
+    pub(crate) registry: ActorRegistry,
+    // IDs of the actors deployed along with the target actor by name, read by the `Fixture`
+    // cheatcode.
+    pub(crate) fixtures: BTreeMap<String, ActorID>,
+    // Gas the message can use as set by the `GasLimit` cheatcode, enforced by the kernel.
+    pub(crate) gas_limit: Option<u64>,
+    // Whether the `GasLimit` cheatcode was used during the last message.
+    pub(crate) gas_limited: bool,
+    // Whether the next message is sent as a read-only call, consumed by its first send.
+    pub(crate) read_only: bool,
+    // First expectation not met during the message, failing it once it is applied.
+    pub(crate) expectation_failure: Option<String>,
+    // Tokens added to the circulating supply by the cheatcodes setting balances, negative if
+    // removed. It is part of the state left by a message, to be passed to the next executors.
+    pub(crate) circ_supply_delta: TokenAmount,
 }
 
 impl<M> KytheraMachine<M>
@@ -25,13 +82,49 @@ where
         let machine = DefaultMachine::new(&context, blockstore, externs)?;
         Ok(KytheraMachine {
             inner: machine,
+            context,
             override_context: OverrideContext::default(),
+            expected_revert: None,
+            revert_caught: false,
+            caught_reverts: vec![],
+            expected_emit: None,
+            emitted_events: vec![],
+            snapshots: vec![],
+            mocked_calls: vec![],
+            mocked_consensus_faults: vec![],
+            mock_proofs: false,
+            expected_proof_verifications: RefCell::new(vec![]),
+            expected_calls: vec![],
+            keyring: Keyring::new(),
+            mock_signatures: None,
+            registry: ActorRegistry::new(),
+            fixtures: BTreeMap::new(),
+            gas_limit: None,
+            gas_limited: false,
+            read_only: false,
+            expectation_failure: None,
+            circ_supply_delta: TokenAmount::default(),
         })
     }
 
     pub fn override_context(&self) -> &OverrideContext {
         &self.override_context
     }
+
+    pub fn keyring(&self) -> &Keyring {
+        &self.keyring
+    }
+
+    /// Set the base fee charged for the gas used by the messages.
+    pub(crate) fn set_base_fee(&mut self, base_fee: TokenAmount) {
+        self.context.base_fee = base_fee;
+    }
+
+    /// Set the current epoch, against which the kernel checks the tipset CID lookbacks.
+    pub(crate) fn set_epoch(&mut self, epoch: ChainEpoch) {
+        self.override_context.epoch = Some(epoch);
+        self.context.epoch = epoch;
+    }
 }
 
 impl<M> Machine for KytheraMachine<M>
@@ -47,7 +140,7 @@ where
     }
 
     fn context(&self) -> &MachineContext {
-        self.inner.context()
+        &self.context
     }
 
     fn externs(&self) -> &Self::Externs {
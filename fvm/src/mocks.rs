@@ -0,0 +1,75 @@
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+use fvm_shared::consensus::ConsensusFault;
+use fvm_shared::error::ExitCode;
+use fvm_shared::{ActorID, MethodNum};
+
+/// Call stubbed by the `MockCall` cheatcode, answered without running the callee.
+#[derive(Debug, Clone)]
+pub struct MockedCall {
+    /// The address the mocked calls are sent to.
+    pub to: Address,
+
+    /// The actor ID of the address, if it is assigned to an actor.
+    pub to_id: Option<ActorID>,
+
+    /// The method of the mocked calls.
+    pub method: MethodNum,
+
+    /// The parameters of the mocked calls, any parameters match if none.
+    pub params: Option<RawBytes>,
+
+    /// The bytes returned by the mocked calls.
+    pub return_bytes: RawBytes,
+
+    /// The exit code returned by the mocked calls.
+    pub exit_code: ExitCode,
+}
+
+/// Consensus fault evidence evaluation stubbed by the `MockConsensusFault` cheatcode.
+#[derive(Debug, Clone)]
+pub struct MockedConsensusFault {
+    /// The first block header of the evidence.
+    pub h1: Vec<u8>,
+
+    /// The second block header of the evidence.
+    pub h2: Vec<u8>,
+
+    /// The extra data of the evidence.
+    pub extra: Vec<u8>,
+
+    /// The fault returned for the evidence, none if it is not a fault.
+    pub fault: Option<ConsensusFault>,
+}
+
+/// Proof verification syscalls answered by the `MockProofs` and `ExpectVerifyProof` cheatcodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofKind {
+    Seal,
+    Post,
+    AggregateSeals,
+    ReplicaUpdate,
+}
+
+impl ProofKind {
+    /// Get the proof kind from its name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Seal" => Some(Self::Seal),
+            "Post" => Some(Self::Post),
+            "AggregateSeals" => Some(Self::AggregateSeals),
+            "ReplicaUpdate" => Some(Self::ReplicaUpdate),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the proof kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Seal => "Seal",
+            Self::Post => "Post",
+            Self::AggregateSeals => "AggregateSeals",
+            Self::ReplicaUpdate => "ReplicaUpdate",
+        }
+    }
+}
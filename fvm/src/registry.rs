@@ -0,0 +1,43 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use cid::Cid;
+use fvm_shared::MethodNum;
+use std::collections::HashMap;
+
+/// Actor whose code is available to the `Deploy` cheatcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisteredActor {
+    /// CID of the actor code in the blockstore.
+    pub code: Cid,
+    /// Method number of the constructor of the actor, if it has one.
+    pub constructor: Option<MethodNum>,
+}
+
+/// Actors that can be deployed by the cheatcodes, by name.
+#[derive(Debug, Clone, Default)]
+pub struct ActorRegistry {
+    actors: HashMap<String, RegisteredActor>,
+}
+
+impl ActorRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an actor under a name, replacing any actor registered under the same name.
+    pub fn register(&mut self, name: String, actor: RegisteredActor) {
+        self.actors.insert(name, actor);
+    }
+
+    /// Get an actor by name.
+    pub fn get(&self, name: &str) -> Option<&RegisteredActor> {
+        self.actors.get(name)
+    }
+
+    /// Get an actor by code CID.
+    pub fn get_by_code(&self, code: &Cid) -> Option<&RegisteredActor> {
+        self.actors.values().find(|actor| &actor.code == code)
+    }
+}
@@ -0,0 +1,15 @@
+use fvm::state_tree::ActorState;
+use fvm_shared::econ::TokenAmount;
+
+/// State of the machine recorded by the `Snapshot` cheatcode, to be restored by `RevertTo`.
+///
+/// The state tree can not be flushed in the middle of a message, so instead of its root the
+/// snapshot holds the state of every actor that has been assigned an ID.
+#[derive(Debug, Clone)]
+pub struct StateSnapshot {
+    /// The state of the actors, indexed by their ID.
+    pub actors: Vec<Option<ActorState>>,
+
+    /// The tokens added to the circulating supply by the cheatcodes setting balances.
+    pub circ_supply_delta: TokenAmount,
+}
@@ -8,6 +8,33 @@ pub(crate) const PRANK_NUM: u64 = 3950310035;
 pub(crate) const TRICK_NUM: u64 = 4270775027;
 pub(crate) const LOG_NUM: u64 = 340034372;
 pub(crate) const ALTER_NUM: u64 = 3679152210;
+pub(crate) const EXPECT_REVERT_NUM: u64 = 1226454194;
+pub(crate) const DEAL_NUM: u64 = 893571786;
+pub(crate) const EXPECT_EMIT_NUM: u64 = 3980849362;
+pub(crate) const SNAPSHOT_NUM: u64 = 2816149230;
+pub(crate) const REVERT_TO_NUM: u64 = 1746618123;
+pub(crate) const START_PRANK_NUM: u64 = 2690012584;
+pub(crate) const STOP_PRANK_NUM: u64 = 3068408437;
+pub(crate) const MOCK_CALL_NUM: u64 = 3656060521;
+pub(crate) const CLEAR_MOCKED_CALLS_NUM: u64 = 324802741;
+pub(crate) const EXPECT_CALL_NUM: u64 = 605728633;
+pub(crate) const SET_CHAIN_RANDOMNESS_NUM: u64 = 1535668960;
+pub(crate) const SET_BEACON_RANDOMNESS_NUM: u64 = 357396257;
+pub(crate) const SET_TIPSET_CID_NUM: u64 = 1999223390;
+pub(crate) const MOCK_CONSENSUS_FAULT_NUM: u64 = 3932931941;
+pub(crate) const MOCK_PROOFS_NUM: u64 = 437281190;
+pub(crate) const EXPECT_VERIFY_PROOF_NUM: u64 = 2724341561;
+pub(crate) const NEW_ACCOUNT_NUM: u64 = 2633295992;
+pub(crate) const ADDR_FROM_KEY_NUM: u64 = 313620791;
+pub(crate) const SIGN_NUM: u64 = 2056743134;
+pub(crate) const MOCK_SIGNATURES_NUM: u64 = 4158214454;
+pub(crate) const DEPLOY_NUM: u64 = 2682775107;
+pub(crate) const GAS_LIMIT_NUM: u64 = 2600761654;
+pub(crate) const LOAD_NUM: u64 = 2875133457;
+pub(crate) const LOAD_BLOCK_NUM: u64 = 3034915863;
+pub(crate) const STORE_NUM: u64 = 3113294559;
+pub(crate) const ALTER_WITH_NUM: u64 = 4274241807;
+pub(crate) const FIXTURE_NUM: u64 = 3309963876;
 
 #[cfg(test)]
 mod test {
@@ -23,6 +50,33 @@ mod test {
     pub(crate) const TRICK_METHOD: &str = "Trick";
     pub(crate) const LOG_METHOD: &str = "Log";
     pub(crate) const ALTER_METHOD: &str = "Alter";
+    pub(crate) const EXPECT_REVERT_METHOD: &str = "ExpectRevert";
+    pub(crate) const DEAL_METHOD: &str = "Deal";
+    pub(crate) const EXPECT_EMIT_METHOD: &str = "ExpectEmit";
+    pub(crate) const SNAPSHOT_METHOD: &str = "Snapshot";
+    pub(crate) const REVERT_TO_METHOD: &str = "RevertTo";
+    pub(crate) const START_PRANK_METHOD: &str = "StartPrank";
+    pub(crate) const STOP_PRANK_METHOD: &str = "StopPrank";
+    pub(crate) const MOCK_CALL_METHOD: &str = "MockCall";
+    pub(crate) const CLEAR_MOCKED_CALLS_METHOD: &str = "ClearMockedCalls";
+    pub(crate) const EXPECT_CALL_METHOD: &str = "ExpectCall";
+    pub(crate) const SET_CHAIN_RANDOMNESS_METHOD: &str = "SetChainRandomness";
+    pub(crate) const SET_BEACON_RANDOMNESS_METHOD: &str = "SetBeaconRandomness";
+    pub(crate) const SET_TIPSET_CID_METHOD: &str = "SetTipsetCid";
+    pub(crate) const MOCK_CONSENSUS_FAULT_METHOD: &str = "MockConsensusFault";
+    pub(crate) const MOCK_PROOFS_METHOD: &str = "MockProofs";
+    pub(crate) const EXPECT_VERIFY_PROOF_METHOD: &str = "ExpectVerifyProof";
+    pub(crate) const NEW_ACCOUNT_METHOD: &str = "NewAccount";
+    pub(crate) const ADDR_FROM_KEY_METHOD: &str = "AddrFromKey";
+    pub(crate) const SIGN_METHOD: &str = "Sign";
+    pub(crate) const MOCK_SIGNATURES_METHOD: &str = "MockSignatures";
+    pub(crate) const DEPLOY_METHOD: &str = "Deploy";
+    pub(crate) const GAS_LIMIT_METHOD: &str = "GasLimit";
+    pub(crate) const LOAD_METHOD: &str = "Load";
+    pub(crate) const LOAD_BLOCK_METHOD: &str = "LoadBlock";
+    pub(crate) const STORE_METHOD: &str = "Store";
+    pub(crate) const ALTER_WITH_METHOD: &str = "AlterWith";
+    pub(crate) const FIXTURE_METHOD: &str = "Fixture";
 
     #[test]
     fn test_cheatcodes_number() {
@@ -34,5 +88,83 @@ mod test {
         assert_eq!(TRICK_NUM, derive_method_num(TRICK_METHOD).unwrap());
         assert_eq!(LOG_NUM, derive_method_num(LOG_METHOD).unwrap());
         assert_eq!(ALTER_NUM, derive_method_num(ALTER_METHOD).unwrap());
+        assert_eq!(
+            EXPECT_REVERT_NUM,
+            derive_method_num(EXPECT_REVERT_METHOD).unwrap()
+        );
+        assert_eq!(DEAL_NUM, derive_method_num(DEAL_METHOD).unwrap());
+        assert_eq!(
+            EXPECT_EMIT_NUM,
+            derive_method_num(EXPECT_EMIT_METHOD).unwrap()
+        );
+        assert_eq!(SNAPSHOT_NUM, derive_method_num(SNAPSHOT_METHOD).unwrap());
+        assert_eq!(REVERT_TO_NUM, derive_method_num(REVERT_TO_METHOD).unwrap());
+        assert_eq!(
+            START_PRANK_NUM,
+            derive_method_num(START_PRANK_METHOD).unwrap()
+        );
+        assert_eq!(
+            STOP_PRANK_NUM,
+            derive_method_num(STOP_PRANK_METHOD).unwrap()
+        );
+        assert_eq!(MOCK_CALL_NUM, derive_method_num(MOCK_CALL_METHOD).unwrap());
+        assert_eq!(
+            CLEAR_MOCKED_CALLS_NUM,
+            derive_method_num(CLEAR_MOCKED_CALLS_METHOD).unwrap()
+        );
+        assert_eq!(
+            EXPECT_CALL_NUM,
+            derive_method_num(EXPECT_CALL_METHOD).unwrap()
+        );
+        assert_eq!(
+            SET_CHAIN_RANDOMNESS_NUM,
+            derive_method_num(SET_CHAIN_RANDOMNESS_METHOD).unwrap()
+        );
+        assert_eq!(
+            SET_BEACON_RANDOMNESS_NUM,
+            derive_method_num(SET_BEACON_RANDOMNESS_METHOD).unwrap()
+        );
+        assert_eq!(
+            SET_TIPSET_CID_NUM,
+            derive_method_num(SET_TIPSET_CID_METHOD).unwrap()
+        );
+        assert_eq!(
+            MOCK_CONSENSUS_FAULT_NUM,
+            derive_method_num(MOCK_CONSENSUS_FAULT_METHOD).unwrap()
+        );
+        assert_eq!(
+            MOCK_PROOFS_NUM,
+            derive_method_num(MOCK_PROOFS_METHOD).unwrap()
+        );
+        assert_eq!(
+            EXPECT_VERIFY_PROOF_NUM,
+            derive_method_num(EXPECT_VERIFY_PROOF_METHOD).unwrap()
+        );
+        assert_eq!(
+            NEW_ACCOUNT_NUM,
+            derive_method_num(NEW_ACCOUNT_METHOD).unwrap()
+        );
+        assert_eq!(
+            ADDR_FROM_KEY_NUM,
+            derive_method_num(ADDR_FROM_KEY_METHOD).unwrap()
+        );
+        assert_eq!(SIGN_NUM, derive_method_num(SIGN_METHOD).unwrap());
+        assert_eq!(
+            MOCK_SIGNATURES_NUM,
+            derive_method_num(MOCK_SIGNATURES_METHOD).unwrap()
+        );
+        assert_eq!(DEPLOY_NUM, derive_method_num(DEPLOY_METHOD).unwrap());
+        assert_eq!(GAS_LIMIT_NUM, derive_method_num(GAS_LIMIT_METHOD).unwrap());
+        assert_eq!(LOAD_NUM, derive_method_num(LOAD_METHOD).unwrap());
+        assert_eq!(
+            LOAD_BLOCK_NUM,
+            derive_method_num(LOAD_BLOCK_METHOD).unwrap()
+        );
+        assert_eq!(STORE_NUM, derive_method_num(STORE_METHOD).unwrap());
+        assert_eq!(
+            ALTER_WITH_NUM,
+            derive_method_num(ALTER_WITH_METHOD).unwrap()
+        );
+        assert_eq!(FIXTURE_NUM, derive_method_num(FIXTURE_METHOD).unwrap());
     }
 }
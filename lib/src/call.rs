@@ -0,0 +1,216 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::BTreeMap;
+
+use fil_actors_runtime_v10::{BURNT_FUNDS_ACTOR_ID, REWARD_ACTOR_ID};
+use fvm_ipld_encoding::de::DeserializeOwned;
+use fvm_shared::{ActorID, MethodNum};
+use kythera_common::abi::derive_method_num;
+use kythera_fvm::{trace::ExecutionEvent, Address, TokenAmount};
+
+use crate::error::Error;
+use crate::{ApplyRet, ExitCode};
+
+/// Method called by [`crate::Tester::call`], by name or by number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MethodId {
+    Name(String),
+    Num(MethodNum),
+}
+
+impl MethodId {
+    /// Get the method number, derived from the name following FRC-042 for named methods.
+    pub fn num(&self) -> Result<MethodNum, Error> {
+        match self {
+            MethodId::Name(name) => derive_method_num(name).map_err(|err| Error::Tester {
+                msg: format!("Could not derive method number of {name}: {err}"),
+                source: None,
+            }),
+            MethodId::Num(num) => Ok(*num),
+        }
+    }
+}
+
+impl From<&str> for MethodId {
+    fn from(name: &str) -> Self {
+        MethodId::Name(name.to_string())
+    }
+}
+
+impl From<String> for MethodId {
+    fn from(name: String) -> Self {
+        MethodId::Name(name)
+    }
+}
+
+impl From<MethodNum> for MethodId {
+    fn from(num: MethodNum) -> Self {
+        MethodId::Num(num)
+    }
+}
+
+/// Output of a message sent to an Actor by [`crate::Tester::call`].
+#[derive(Clone, Debug)]
+pub struct CallResult {
+    /// The [`ApplyRet`] of the message.
+    pub ret: ApplyRet,
+    /// Value sent with the message.
+    pub value: TokenAmount,
+    /// Balance changes of the receiver and of the Actors it called, directly or not, by ID. Gas
+    /// fees are not included.
+    pub balance_changes: BTreeMap<ActorID, TokenAmount>,
+}
+
+impl CallResult {
+    /// Check that the balance changes add up to the value sent if the message succeeded, and to
+    /// zero otherwise. Funds sent back to the testing account or minted by cheatcodes such as
+    /// `Deal` make the check fail.
+    pub fn check_balance_changes(&self) -> Result<(), Error> {
+        let expected = if self.ret.msg_receipt.exit_code == ExitCode::OK {
+            self.value.clone()
+        } else {
+            TokenAmount::default()
+        };
+        let total = self
+            .balance_changes
+            .values()
+            .fold(TokenAmount::default(), |total, change| total + change);
+
+        if total != expected {
+            return Err(Error::Tester {
+                msg: format!(
+                    "Balance changes of the called Actors add up to {} attoFIL, expected {} attoFIL",
+                    total.atto(),
+                    expected.atto()
+                ),
+                source: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Deserialize the CBOR encoded return data of the message.
+    pub fn return_data<R: DeserializeOwned>(&self) -> Result<R, Error> {
+        self.ret
+            .msg_receipt
+            .return_data
+            .deserialize()
+            .map_err(|err| Error::Tester {
+                msg: format!("Could not deserialize return data: {err}"),
+                source: None,
+            })
+    }
+
+    /// Gas fees received by an Actor during the message, not accounted as balance changes.
+    pub(crate) fn fees_received(ret: &ApplyRet, actor_id: ActorID) -> TokenAmount {
+        match actor_id {
+            BURNT_FUNDS_ACTOR_ID => &ret.base_fee_burn + &ret.over_estimation_burn,
+            REWARD_ACTOR_ID => ret.miner_tip.clone(),
+            _ => TokenAmount::default(),
+        }
+    }
+}
+
+/// Addresses of the Actors called during a message, in order of their first call.
+pub(crate) fn called_addresses(ret: &ApplyRet) -> Vec<Address> {
+    let mut addresses = vec![];
+    for event in &ret.exec_trace {
+        if let ExecutionEvent::Call { to, .. } = event {
+            if !addresses.contains(to) {
+                addresses.push(*to);
+            }
+        }
+    }
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fvm_ipld_encoding::RawBytes;
+    use kythera_fvm::Receipt;
+
+    fn apply_ret(exit_code: ExitCode) -> ApplyRet {
+        ApplyRet {
+            msg_receipt: Receipt {
+                exit_code,
+                return_data: Default::default(),
+                gas_used: 0,
+                events_root: None,
+            },
+            penalty: TokenAmount::default(),
+            miner_tip: TokenAmount::from_atto(5),
+            base_fee_burn: TokenAmount::from_atto(10),
+            over_estimation_burn: TokenAmount::from_atto(1),
+            refund: TokenAmount::default(),
+            gas_refund: 0,
+            gas_burned: 0,
+            failure_info: None,
+            exec_trace: vec![],
+            events: vec![],
+        }
+    }
+
+    #[test]
+    fn test_check_balance_changes() {
+        let result = CallResult {
+            ret: apply_ret(ExitCode::OK),
+            value: TokenAmount::from_atto(100),
+            balance_changes: BTreeMap::from([
+                (1001, TokenAmount::from_atto(60)),
+                (1002, TokenAmount::from_atto(40)),
+            ]),
+        };
+        assert!(result.check_balance_changes().is_ok());
+
+        // The value is not transferred when the message fails.
+        let failed = CallResult {
+            ret: apply_ret(ExitCode::USR_ILLEGAL_STATE),
+            ..result.clone()
+        };
+        assert_eq!(
+            failed.check_balance_changes().unwrap_err().to_string(),
+            "Balance changes of the called Actors add up to 100 attoFIL, expected 0 attoFIL"
+        );
+
+        assert_eq!(
+            CallResult::fees_received(&result.ret, BURNT_FUNDS_ACTOR_ID),
+            TokenAmount::from_atto(11)
+        );
+        assert_eq!(
+            CallResult::fees_received(&result.ret, REWARD_ACTOR_ID),
+            TokenAmount::from_atto(5)
+        );
+        assert_eq!(
+            CallResult::fees_received(&result.ret, 1001),
+            TokenAmount::default()
+        );
+    }
+
+    #[test]
+    fn test_method_id() {
+        assert_eq!(MethodId::from(2).num().unwrap(), 2);
+        assert_eq!(
+            MethodId::from("HelloWorld").num().unwrap(),
+            derive_method_num("HelloWorld").unwrap()
+        );
+        assert!(MethodId::from("helloWorld").num().is_err());
+    }
+
+    #[test]
+    fn test_return_data() {
+        let mut ret = apply_ret(ExitCode::OK);
+        ret.msg_receipt.return_data = RawBytes::serialize((103u64, "Basic Target Actor")).unwrap();
+        let result = CallResult {
+            ret,
+            value: TokenAmount::default(),
+            balance_changes: BTreeMap::new(),
+        };
+
+        let (id, name): (u64, String) = result.return_data().unwrap();
+        assert_eq!(id, 103);
+        assert_eq!(name, "Basic Target Actor");
+        assert!(result.return_data::<bool>().is_err());
+    }
+}
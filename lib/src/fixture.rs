@@ -0,0 +1,23 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::ActorID;
+use kythera_fvm::TokenAmount;
+
+use crate::WasmActor;
+
+/// Actor deployed along with the target Actor, whose ID is read by name by the test methods.
+#[derive(Clone, Debug)]
+pub struct FixtureActor {
+    /// Name under which the Actor ID is read by the test methods.
+    pub name: String,
+    /// The WebAssembly Actor to deploy.
+    pub actor: WasmActor,
+    /// Parameters of the Actor constructor.
+    pub constructor_params: RawBytes,
+    /// Balance the Actor is provided with.
+    pub balance: TokenAmount,
+    /// ID the Actor is deployed at, the next available one if none.
+    pub id: Option<ActorID>,
+}
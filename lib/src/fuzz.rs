@@ -0,0 +1,433 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use core::fmt;
+
+use fvm_ipld_encoding::serde::{ser::SerializeSeq, Serialize, Serializer};
+use fvm_ipld_encoding::{to_vec, RawBytes};
+use fvm_shared::{address::Address, ActorID};
+use kythera_common::abi::ParamType;
+use rand::{distributions::Alphanumeric, Rng};
+
+/// Maximum length of generated `bytes` and `string` values.
+const MAX_BYTES_LEN: usize = 32;
+/// Maximum length of generated arrays.
+const MAX_ARRAY_LEN: usize = 8;
+/// Maximum number of runs executed when shrinking a counterexample. A single `u64` input is shrunk
+/// to the smallest failing value in less than 200 runs, whatever its generated value.
+pub(crate) const MAX_SHRINK_RUNS: u32 = 1024;
+
+/// Configuration used when running `TestFuzz` methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Number of runs with generated inputs for each `TestFuzz` method.
+    pub runs: u32,
+    /// Seed of the inputs generator.
+    pub seed: u64,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self { runs: 256, seed: 0 }
+    }
+}
+
+/// A value generated from a [`ParamType`] and passed to a `TestFuzz` method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FuzzValue {
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    Bytes(Vec<u8>),
+    String(String),
+    Address(Address),
+    Array(Vec<FuzzValue>),
+}
+
+impl FuzzValue {
+    /// Generate a random value for the given [`ParamType`].
+    /// Integers are biased towards their edge values.
+    pub fn generate<R: Rng>(param: &ParamType, rng: &mut R) -> Self {
+        match param {
+            ParamType::Bool => FuzzValue::Bool(rng.gen()),
+            ParamType::U64 => FuzzValue::U64(generate_u64(rng)),
+            ParamType::I64 => FuzzValue::I64(match rng.gen_range(0..8) {
+                0 => 0,
+                1 => i64::MIN,
+                2 => i64::MAX,
+                _ => rng.gen(),
+            }),
+            ParamType::Bytes => {
+                let len = rng.gen_range(0..=MAX_BYTES_LEN);
+                FuzzValue::Bytes((0..len).map(|_| rng.gen()).collect())
+            }
+            ParamType::String => {
+                let len = rng.gen_range(0..=MAX_BYTES_LEN);
+                FuzzValue::String(
+                    rng.sample_iter(&Alphanumeric)
+                        .take(len)
+                        .map(char::from)
+                        .collect(),
+                )
+            }
+            ParamType::Address => FuzzValue::Address(Address::new_id(generate_u64(rng))),
+            ParamType::Array(inner) => {
+                let len = rng.gen_range(0..=MAX_ARRAY_LEN);
+                FuzzValue::Array((0..len).map(|_| Self::generate(inner, rng)).collect())
+            }
+        }
+    }
+
+    /// Return simpler candidates for this value, the simplest first.
+    pub fn shrink(&self) -> Vec<FuzzValue> {
+        match self {
+            FuzzValue::Bool(true) => vec![FuzzValue::Bool(false)],
+            FuzzValue::Bool(false) => vec![],
+            FuzzValue::U64(n) => shrink_u64(*n).into_iter().map(FuzzValue::U64).collect(),
+            FuzzValue::I64(n) => shrink_i64(*n).into_iter().map(FuzzValue::I64).collect(),
+            FuzzValue::Bytes(bytes) => {
+                let mut candidates: Vec<FuzzValue> = shrink_len(bytes)
+                    .into_iter()
+                    .map(FuzzValue::Bytes)
+                    .collect();
+                for (i, byte) in bytes.iter().enumerate() {
+                    if *byte != 0 {
+                        let mut zeroed = bytes.clone();
+                        zeroed[i] = 0;
+                        candidates.push(FuzzValue::Bytes(zeroed));
+                    }
+                }
+                candidates
+            }
+            FuzzValue::String(s) => {
+                let chars = s.chars().collect::<Vec<char>>();
+                shrink_len(&chars)
+                    .into_iter()
+                    .map(|chars| FuzzValue::String(chars.into_iter().collect()))
+                    .collect()
+            }
+            FuzzValue::Address(address) => match address.id() {
+                Ok(id) => shrink_u64(id)
+                    .into_iter()
+                    .map(|id| FuzzValue::Address(Address::new_id(id)))
+                    .collect(),
+                Err(_) => vec![],
+            },
+            FuzzValue::Array(values) => {
+                let mut candidates: Vec<FuzzValue> = shrink_len(values)
+                    .into_iter()
+                    .map(FuzzValue::Array)
+                    .collect();
+                for (i, value) in values.iter().enumerate() {
+                    for shrunk in value.shrink() {
+                        let mut values = values.clone();
+                        values[i] = shrunk;
+                        candidates.push(FuzzValue::Array(values));
+                    }
+                }
+                candidates
+            }
+        }
+    }
+}
+
+/// Generate a random `u64`, biased towards its edge values.
+fn generate_u64<R: Rng>(rng: &mut R) -> u64 {
+    match rng.gen_range(0..8) {
+        0 => 0,
+        1 => 1,
+        2 => u64::MAX,
+        _ => rng.gen(),
+    }
+}
+
+/// Candidates closer to zero for an `u64`, first the biggest steps then the smallest.
+fn shrink_u64(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut candidates = vec![0];
+    let mut step = n / 2;
+    while step > 0 {
+        if n - step != 0 {
+            candidates.push(n - step);
+        }
+        step /= 2;
+    }
+    candidates
+}
+
+/// Candidates closer to zero for an `i64`, first the biggest steps then the smallest.
+fn shrink_i64(n: i64) -> Vec<i64> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut candidates = vec![0];
+    let mut step = n / 2;
+    while step != 0 {
+        if n - step != 0 {
+            candidates.push(n - step);
+        }
+        step /= 2;
+    }
+    candidates
+}
+
+/// Shorter candidates for a sequence: empty, halved and without its last element.
+fn shrink_len<T: Clone>(values: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+    if values.is_empty() {
+        return candidates;
+    }
+
+    candidates.push(vec![]);
+    if values.len() > 2 {
+        candidates.push(values[..values.len() / 2].to_vec());
+    }
+    if values.len() > 1 {
+        candidates.push(values[..values.len() - 1].to_vec());
+    }
+    candidates
+}
+
+impl Serialize for FuzzValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            FuzzValue::Bool(b) => serializer.serialize_bool(*b),
+            FuzzValue::U64(n) => serializer.serialize_u64(*n),
+            FuzzValue::I64(n) => serializer.serialize_i64(*n),
+            FuzzValue::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            FuzzValue::String(s) => serializer.serialize_str(s),
+            FuzzValue::Address(address) => address.serialize(serializer),
+            FuzzValue::Array(values) => serializer.collect_seq(values),
+        }
+    }
+}
+
+impl fmt::Display for FuzzValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzValue::Bool(b) => write!(f, "{b}"),
+            FuzzValue::U64(n) => write!(f, "{n}"),
+            FuzzValue::I64(n) => write!(f, "{n}"),
+            FuzzValue::Bytes(bytes) => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+            FuzzValue::String(s) => write!(f, "{s:?}"),
+            FuzzValue::Address(address) => write!(f, "{address}"),
+            FuzzValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Generate the inputs for a `TestFuzz` method parameters.
+pub(crate) fn generate_inputs<R: Rng>(params: &[ParamType], rng: &mut R) -> Vec<FuzzValue> {
+    params
+        .iter()
+        .map(|param| FuzzValue::generate(param, rng))
+        .collect()
+}
+
+/// Return simpler candidates for a list of inputs, each candidate shrinks a single input.
+pub(crate) fn shrink_inputs(inputs: &[FuzzValue]) -> Vec<Vec<FuzzValue>> {
+    let mut candidates = vec![];
+    for (i, input) in inputs.iter().enumerate() {
+        for shrunk in input.shrink() {
+            let mut candidate = inputs.to_vec();
+            candidate[i] = shrunk;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Shrink failing inputs to simpler ones that still fail, `run` returning the failure of the
+/// candidates that fail, until no candidate fails or [`MAX_SHRINK_RUNS`] runs are executed.
+/// Return the simplest failing inputs found, their failure and the number of runs executed.
+pub(crate) fn shrink<T>(
+    mut inputs: Vec<FuzzValue>,
+    mut failure: T,
+    mut run: impl FnMut(&[FuzzValue]) -> Option<T>,
+) -> (Vec<FuzzValue>, T, u32) {
+    let mut shrink_runs = 0;
+    'shrink: loop {
+        for candidate in shrink_inputs(&inputs) {
+            if shrink_runs >= MAX_SHRINK_RUNS {
+                break 'shrink;
+            }
+            shrink_runs += 1;
+
+            if let Some(candidate_failure) = run(&candidate) {
+                inputs = candidate;
+                failure = candidate_failure;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    (inputs, failure, shrink_runs)
+}
+
+/// Parameters of a method called with generated inputs, optionally preceded by the target Actor ID.
+struct FuzzParams<'a>(Option<ActorID>, &'a [FuzzValue]);
+
+impl Serialize for FuzzParams<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = self.1.len() + usize::from(self.0.is_some());
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        if let Some(target_id) = self.0 {
+            seq.serialize_element(&target_id)?;
+        }
+        for input in self.1 {
+            seq.serialize_element(input)?;
+        }
+        seq.end()
+    }
+}
+
+/// Encode the parameters of a `TestFuzz` method, the target Actor ID followed by the inputs.
+pub(crate) fn encode_params(target_id: ActorID, inputs: &[FuzzValue]) -> RawBytes {
+    RawBytes::new(
+        to_vec(&FuzzParams(Some(target_id), inputs))
+            .expect("Should be able to serialize fuzz inputs"),
+    )
+}
+
+/// Encode generated inputs as the parameters of a target Actor method.
+pub(crate) fn encode_inputs(inputs: &[FuzzValue]) -> RawBytes {
+    RawBytes::new(
+        to_vec(&FuzzParams(None, inputs)).expect("Should be able to serialize fuzz inputs"),
+    )
+}
+
+/// Report of the runs of a `TestFuzz` method.
+#[derive(Clone, Debug)]
+pub struct FuzzReport {
+    runs: u32,
+    counterexample: Option<Vec<FuzzValue>>,
+}
+
+impl FuzzReport {
+    pub(crate) fn new(runs: u32, counterexample: Option<Vec<FuzzValue>>) -> Self {
+        Self {
+            runs,
+            counterexample,
+        }
+    }
+
+    /// Get the number of runs executed.
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+
+    /// Get the smallest inputs found for which the method failed.
+    pub fn counterexample(&self) -> Option<&[FuzzValue]> {
+        self.counterexample.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fvm_ipld_encoding::from_slice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let params = vec![
+            ParamType::U64,
+            ParamType::String,
+            ParamType::Array(Box::new(ParamType::Address)),
+        ];
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+        let inputs = generate_inputs(&params, &mut rng);
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+        assert_eq!(inputs, generate_inputs(&params, &mut rng));
+
+        assert!(matches!(inputs[0], FuzzValue::U64(_)));
+        assert!(matches!(inputs[1], FuzzValue::String(_)));
+        assert!(matches!(inputs[2], FuzzValue::Array(_)));
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(
+            FuzzValue::U64(10).shrink(),
+            vec![
+                FuzzValue::U64(0),
+                FuzzValue::U64(5),
+                FuzzValue::U64(8),
+                FuzzValue::U64(9)
+            ]
+        );
+        assert_eq!(
+            FuzzValue::I64(-4).shrink(),
+            vec![FuzzValue::I64(0), FuzzValue::I64(-2), FuzzValue::I64(-3)]
+        );
+        assert!(FuzzValue::U64(0).shrink().is_empty());
+        assert!(FuzzValue::Bool(false).shrink().is_empty());
+        assert_eq!(
+            FuzzValue::String(String::from("abc")).shrink(),
+            vec![
+                FuzzValue::String(String::new()),
+                FuzzValue::String(String::from("a")),
+                FuzzValue::String(String::from("ab"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shrink_u64_within_max_runs() {
+        // A run fails for values of at least 1000.
+        for start in [1000, 1001, 4096, u64::MAX / 2 + 1000, u64::MAX] {
+            let (inputs, _, shrink_runs) = shrink(vec![FuzzValue::U64(start)], (), |candidate| {
+                matches!(candidate[0], FuzzValue::U64(value) if value >= 1000).then_some(())
+            });
+
+            assert_eq!(inputs, vec![FuzzValue::U64(1000)]);
+            assert!(shrink_runs < 200, "{start} shrunk in {shrink_runs} runs");
+        }
+    }
+
+    #[test]
+    fn test_shrink_stops_at_max_runs() {
+        // Each input has 4 candidates, none of them failing.
+        let inputs = vec![FuzzValue::U64(10); 300];
+        let (shrunk, _, shrink_runs) = shrink(inputs.clone(), (), |_| None);
+
+        assert_eq!(shrunk, inputs);
+        assert_eq!(shrink_runs, MAX_SHRINK_RUNS);
+    }
+
+    #[test]
+    fn test_encode_params() {
+        let inputs = vec![FuzzValue::U64(3), FuzzValue::Bytes(vec![1, 2])];
+        let params = encode_params(103, &inputs);
+
+        let (target_id, value, bytes): (u64, u64, fvm_ipld_encoding::BytesDe) =
+            from_slice(params.bytes()).unwrap();
+        assert_eq!(target_id, 103);
+        assert_eq!(value, 3);
+        assert_eq!(bytes.0, vec![1, 2]);
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use core::fmt;
+use std::collections::BTreeMap;
+
+use cid::Cid;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::ActorID;
+use kythera_common::abi::Method;
+use kythera_fvm::{
+    engine::EnginePool,
+    executor::{GasConfig, KytheraExecutor},
+    externs::FakeExterns,
+    keys::Keyring,
+    registry::ActorRegistry,
+    Address, TokenAmount,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::fuzz::{self, FuzzValue};
+use crate::{ApplyRet, TestResultType};
+
+/// Maximum number of call sequences replayed when minimizing a failing sequence.
+const MAX_SEQUENCE_SHRINK_RUNS: u32 = 256;
+
+/// Configuration used when running `Invariant` methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvariantConfig {
+    /// Number of call sequences executed.
+    pub runs: u32,
+    /// Number of calls to the target Actor in each sequence.
+    pub depth: u32,
+}
+
+impl Default for InvariantConfig {
+    fn default() -> Self {
+        Self {
+            runs: 32,
+            depth: 15,
+        }
+    }
+}
+
+/// A call to an entrypoint of the target Actor made by the invariant driver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvariantCall {
+    method: Method,
+    inputs: Vec<FuzzValue>,
+}
+
+impl InvariantCall {
+    /// Get the called [`Method`].
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Get the inputs generated for the call.
+    pub fn inputs(&self) -> &[FuzzValue] {
+        &self.inputs
+    }
+}
+
+impl fmt::Display for InvariantCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.method)?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{input}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Report of the runs of an `Invariant` method.
+#[derive(Clone, Debug)]
+pub struct InvariantReport {
+    runs: u32,
+    calls: u64,
+    counterexample: Option<Vec<InvariantCall>>,
+}
+
+impl InvariantReport {
+    /// Get the number of call sequences executed.
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+
+    /// Get the total number of calls made to the target Actor.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Get the minimized call sequence after which the invariant failed.
+    pub fn counterexample(&self) -> Option<&[InvariantCall]> {
+        self.counterexample.as_deref()
+    }
+}
+
+/// Driver calling random entrypoints of the target Actor and checking the `Invariant` methods
+/// of the test Actor after each call.
+pub(crate) struct InvariantCampaign<'a> {
+    pub engine: &'a EnginePool,
+    pub externs: &'a FakeExterns,
+    pub keyring: &'a Keyring,
+    pub registry: &'a ActorRegistry,
+    pub fixtures: &'a BTreeMap<String, ActorID>,
+    pub gas_config: &'a GasConfig,
+    pub builtin_actors: Cid,
+    pub account_address: Address,
+    pub target_address: Address,
+    pub target_id: RawBytes,
+    pub test_address: Address,
+    pub entrypoints: Vec<&'a Method>,
+    pub invariants: Vec<&'a Method>,
+    // State the call sequences start from.
+    pub root: Cid,
+    pub blockstore: MemoryBlockstore,
+    pub circ_supply_delta: TokenAmount,
+    // Account sequence at the start of the call sequences.
+    pub sequence: u64,
+    pub config: InvariantConfig,
+    pub seed: u64,
+}
+
+impl InvariantCampaign<'_> {
+    /// Run the call sequences, returning the outcome of each `Invariant` method in order.
+    pub fn run(self) -> Vec<(TestResultType, InvariantReport)> {
+        let mut outcomes: Vec<Option<(TestResultType, InvariantReport)>> =
+            vec![None; self.invariants.len()];
+        let mut last_passed: Vec<Option<ApplyRet>> = vec![None; self.invariants.len()];
+
+        // Invariants should hold on the initial state.
+        for (i, invariant) in self.invariants.iter().enumerate() {
+            match self.check(
+                self.root,
+                &self.blockstore,
+                &self.circ_supply_delta,
+                self.sequence,
+                invariant,
+            ) {
+                TestResultType::Passed(ret) => last_passed[i] = Some(ret),
+                ret if ret.is_failure() => outcomes[i] = Some((ret, report(0, 0, Some(vec![])))),
+                ret => outcomes[i] = Some((ret, report(0, 0, None))),
+            }
+        }
+
+        if self.entrypoints.is_empty() {
+            log::warn!("No entrypoint on target Actor to run invariants against");
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut runs = 0;
+        let mut calls_count = 0;
+        'runs: while runs < self.config.runs
+            && !self.entrypoints.is_empty()
+            && outcomes.iter().any(Option::is_none)
+        {
+            runs += 1;
+            let mut root = self.root;
+            let mut blockstore = self.blockstore.clone();
+            let mut circ_supply_delta = self.circ_supply_delta.clone();
+            let mut sequence = self.sequence;
+            let mut calls = vec![];
+
+            for _ in 0..self.config.depth {
+                let method = self.entrypoints[rng.gen_range(0..self.entrypoints.len())];
+                let call = InvariantCall {
+                    method: method.clone(),
+                    inputs: fuzz::generate_inputs(method.params(), &mut rng),
+                };
+
+                match self.call(root, blockstore, circ_supply_delta, sequence, &call) {
+                    Ok((new_root, new_blockstore, new_circ_supply_delta)) => {
+                        root = new_root;
+                        blockstore = new_blockstore;
+                        circ_supply_delta = new_circ_supply_delta;
+                    }
+                    Err(err) => {
+                        for outcome in outcomes.iter_mut().filter(|o| o.is_none()) {
+                            *outcome = Some((
+                                TestResultType::Erred(err.clone()),
+                                report(runs, calls_count, None),
+                            ));
+                        }
+                        break 'runs;
+                    }
+                }
+                sequence += 1;
+                calls_count += 1;
+                calls.push(call);
+
+                for (i, invariant) in self.invariants.iter().enumerate() {
+                    if outcomes[i].is_some() {
+                        continue;
+                    }
+                    match self.check(root, &blockstore, &circ_supply_delta, sequence, invariant) {
+                        TestResultType::Passed(ret) => last_passed[i] = Some(ret),
+                        ret if ret.is_failure() => {
+                            let (calls, ret) = self.minimize(invariant, calls.clone(), ret);
+                            outcomes[i] = Some((ret, report(runs, calls_count, Some(calls))));
+                        }
+                        ret => outcomes[i] = Some((ret, report(runs, calls_count, None))),
+                    }
+                }
+
+                if outcomes.iter().all(Option::is_some) {
+                    break 'runs;
+                }
+            }
+        }
+
+        outcomes
+            .into_iter()
+            .zip(last_passed)
+            .map(|(outcome, last_passed)| {
+                outcome.unwrap_or_else(|| {
+                    (
+                        TestResultType::Passed(
+                            last_passed.expect("Invariant should have been checked at least once"),
+                        ),
+                        report(runs, calls_count, None),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Instantiate a new [`KytheraExecutor`] over the given state.
+    fn executor(
+        &self,
+        root: Cid,
+        blockstore: MemoryBlockstore,
+        circ_supply_delta: TokenAmount,
+    ) -> KytheraExecutor {
+        KytheraExecutor::new(
+            self.engine.clone(),
+            blockstore,
+            root,
+            self.builtin_actors,
+            self.account_address,
+            self.target_id.clone(),
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(circ_supply_delta)
+        .with_gas_config(self.gas_config.clone())
+    }
+
+    /// Apply a call to the target Actor, returning the resulting state.
+    fn call(
+        &self,
+        root: Cid,
+        blockstore: MemoryBlockstore,
+        circ_supply_delta: TokenAmount,
+        sequence: u64,
+        call: &InvariantCall,
+    ) -> Result<(Cid, MemoryBlockstore, TokenAmount), String> {
+        let mut executor = self.executor(root, blockstore, circ_supply_delta);
+        let params = if call.inputs.is_empty() {
+            self.target_id.clone()
+        } else {
+            fuzz::encode_inputs(&call.inputs)
+        };
+        // Payable entrypoints are called with the value of their ABI.
+        let ret = executor.execute_method_with_value(
+            self.target_address,
+            call.method.number(),
+            params,
+            TokenAmount::from_atto(call.method.value()),
+            sequence,
+        );
+
+        match ret {
+            Ok(apply_ret) => {
+                log::trace!(
+                    "Invariant call {call} exited with {}",
+                    apply_ret.msg_receipt.exit_code
+                );
+                let circ_supply_delta = executor.circ_supply_delta().clone();
+                let (root, blockstore) = executor.into_store();
+                Ok((root, blockstore, circ_supply_delta))
+            }
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    /// Check an `Invariant` method over the given state, which is left untouched.
+    fn check(
+        &self,
+        root: Cid,
+        blockstore: &MemoryBlockstore,
+        circ_supply_delta: &TokenAmount,
+        sequence: u64,
+        invariant: &Method,
+    ) -> TestResultType {
+        let mut executor = self.executor(root, blockstore.clone(), circ_supply_delta.clone());
+        let message = executor.execute_method_with_value(
+            self.test_address,
+            invariant.number(),
+            self.target_id.clone(),
+            TokenAmount::from_atto(invariant.value()),
+            sequence,
+        );
+
+        match message {
+            Ok(apply_ret) => TestResultType::applied(apply_ret, false, executor.gas_limited()),
+            Err(err) => TestResultType::erred(err),
+        }
+    }
+
+    /// Replay a call sequence from the initial state, returning the index of the call after
+    /// which the `Invariant` method failed if it did.
+    fn replay(
+        &self,
+        calls: &[InvariantCall],
+        invariant: &Method,
+    ) -> Option<(usize, TestResultType)> {
+        let mut root = self.root;
+        let mut blockstore = self.blockstore.clone();
+        let mut circ_supply_delta = self.circ_supply_delta.clone();
+        let mut sequence = self.sequence;
+
+        for (i, call) in calls.iter().enumerate() {
+            (root, blockstore, circ_supply_delta) = self
+                .call(root, blockstore, circ_supply_delta, sequence, call)
+                .ok()?;
+            sequence += 1;
+
+            match self.check(root, &blockstore, &circ_supply_delta, sequence, invariant) {
+                TestResultType::Passed(_) => {}
+                ret if ret.is_failure() => return Some((i, ret)),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Minimize a call sequence after which the `Invariant` method fails, by first removing calls
+    /// and then shrinking their inputs.
+    fn minimize(
+        &self,
+        invariant: &Method,
+        mut calls: Vec<InvariantCall>,
+        mut ret: TestResultType,
+    ) -> (Vec<InvariantCall>, TestResultType) {
+        let mut shrink_runs = 0;
+        'shrink: loop {
+            for mut candidate in shrink_sequence(&calls) {
+                if shrink_runs >= MAX_SEQUENCE_SHRINK_RUNS {
+                    break 'shrink;
+                }
+                shrink_runs += 1;
+
+                if let Some((index, candidate_ret)) = self.replay(&candidate, invariant) {
+                    candidate.truncate(index + 1);
+                    calls = candidate;
+                    ret = candidate_ret;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        log::debug!(
+            "Minimized call sequence of {}() in {shrink_runs} runs",
+            invariant.name()
+        );
+
+        (calls, ret)
+    }
+}
+
+/// Return simpler candidates for a call sequence, first without one of its calls and then with
+/// the inputs of one of its calls shrunk.
+fn shrink_sequence(calls: &[InvariantCall]) -> Vec<Vec<InvariantCall>> {
+    let mut candidates = vec![];
+    // The initial state has been checked, sequences should at least contain a call.
+    if calls.len() > 1 {
+        for i in 0..calls.len() {
+            let mut candidate = calls.to_vec();
+            candidate.remove(i);
+            candidates.push(candidate);
+        }
+    }
+
+    for (i, call) in calls.iter().enumerate() {
+        for inputs in fuzz::shrink_inputs(&call.inputs) {
+            let mut candidate = calls.to_vec();
+            candidate[i].inputs = inputs;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+fn report(runs: u32, calls: u64, counterexample: Option<Vec<InvariantCall>>) -> InvariantReport {
+    InvariantReport {
+        runs,
+        calls,
+        counterexample,
+    }
+}
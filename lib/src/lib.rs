@@ -5,42 +5,95 @@
 use colored::Colorize;
 
 pub use kythera_common::{
-    abi::{pascal_case_split, Abi, Method, MethodType},
+    abi::{pascal_case_split, Abi, Method, MethodType, ParamType},
     from_slice, to_vec,
 };
 
 pub use kythera_fvm::{
-    executor::{ApplyRet, KytheraExecutor},
+    engine::EnginePool,
+    event::{format_event, StampedEvent},
+    executor::{ApplyRet, GasConfig, KytheraExecutor},
+    externs::{
+        const_consensus_fault, const_randomness, const_tipset, ConsensusFaultSource, FakeExterns,
+        RandomnessSource, TipsetSource,
+    },
+    keys::{KeyType, Keyring},
+    registry::{ActorRegistry, RegisteredActor},
+    state_tree::ActorState,
     trace::ExecutionEvent,
-    Account, Address, ErrorNumber, Gas, GasCharge, Payload, Receipt, SyscallError, TokenAmount,
+    Account, Address, ErrorNumber, ExpectationFailure, Gas, GasCharge, Payload, Receipt,
+    SyscallError, TokenAmount,
 };
 
+use cid::Cid;
 use core::fmt;
-use std::sync::mpsc::SyncSender;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
 
 pub use fvm_ipld_encoding::RawBytes;
-pub use fvm_shared::{bigint::Zero, error::ExitCode};
+use fvm_ipld_encoding::{de::DeserializeOwned, ser::Serialize};
+pub use fvm_shared::ActorID;
+pub use fvm_shared::{bigint::Zero, error::ExitCode, HAMT_BIT_WIDTH};
 
 use crate::validator::validate_wasm_bin;
 use error::Error;
+use fvm_shared::MethodNum;
+use kythera_fvm::keys::{default_secret_key, TestKey};
+use runner::{PreparedTest, Runner};
 use state_tree::{BuiltInActors, StateTree};
 
+pub use call::{CallResult, MethodId};
+pub use fixture::FixtureActor;
+pub use fuzz::{FuzzConfig, FuzzReport, FuzzValue};
+pub use invariant::{InvariantCall, InvariantConfig, InvariantReport};
+
+mod call;
 pub mod error;
+mod fixture;
+mod fuzz;
+mod invariant;
+mod runner;
 mod state_tree;
 mod validator;
 
+/// Balance of the testing account in FIL. It pays the gas fees of the messages, up to the gas
+/// limit times the gas fee cap, 10000 attoFIL not covering a fee cap of 1 attoFIL with the
+/// default gas limit.
+const TESTING_ACCOUNT_BALANCE: i64 = 1_000_000;
+
 /// Main interface to test `Actor`s with Kythera.
 pub struct Tester {
     // Builtin actors root Cid used in the Machine
     builtin_actors: BuiltInActors,
+    // Engine shared between the Executors.
+    engine: EnginePool,
     // State tree constructed before instantiating the Machine
     state_tree: StateTree,
     // Account used for testing.
     account: Account,
     // The Target Actor to be tested.
     target_actor: Option<DeployedActor>,
+    // IDs of the Actors deployed with the target Actor, by name.
+    fixtures: BTreeMap<String, ActorID>,
     // The Method message sequence number.
     sequence: u64,
+    // Configuration of the `TestFuzz` methods runs.
+    fuzz_config: FuzzConfig,
+    // Configuration of the `Invariant` methods runs.
+    invariant_config: InvariantConfig,
+    // Gas settings of the executed messages.
+    gas_config: GasConfig,
+    // Number of threads running the test methods.
+    jobs: usize,
+    // Externs providing randomness and chain data to the Machines.
+    externs: FakeExterns,
+    // Keys and labels of the test accounts.
+    keyring: Keyring,
+    // Actors that can be deployed by the `Deploy` cheatcode.
+    registry: ActorRegistry,
+    // Tokens added to the circulating supply by the cheatcodes setting balances.
+    circ_supply_delta: TokenAmount,
 }
 
 /// WebAssembly Actor.
@@ -49,6 +102,10 @@ pub struct WasmActor {
     name: String,
     bytecode: Vec<u8>,
     abi: Abi,
+    // Parameters sent to the constructor, the target Actor ID if none.
+    constructor_params: Option<Vec<u8>>,
+    // Value sent to the constructor by the testing account.
+    constructor_value: TokenAmount,
 }
 
 impl WasmActor {
@@ -58,9 +115,18 @@ impl WasmActor {
             name,
             bytecode,
             abi,
+            constructor_params: None,
+            constructor_value: TokenAmount::zero(),
         }
     }
 
+    /// Set the CBOR parameters and the value sent to the Actor constructor.
+    pub fn with_constructor_params(mut self, params: RawBytes, value: TokenAmount) -> Self {
+        self.constructor_params = Some(params.into());
+        self.constructor_value = value;
+        self
+    }
+
     /// Get the WebAssembly Actor name.
     pub fn name(&self) -> &str {
         &self.name
@@ -76,6 +142,16 @@ impl WasmActor {
         &self.abi
     }
 
+    /// Get the parameters and the value sent to the Actor constructor, the given default
+    /// parameters being sent if none were set.
+    fn constructor_args(&self, default_params: RawBytes) -> (RawBytes, TokenAmount) {
+        let params = self
+            .constructor_params
+            .clone()
+            .map_or(default_params, RawBytes::new);
+        (params, self.constructor_value.clone())
+    }
+
     /// Convert into a [`DeployedActor`].
     pub fn deploy(self, address: Address) -> DeployedActor {
         DeployedActor {
@@ -129,20 +205,69 @@ impl DeployedActor {
 pub enum TestResultType {
     Passed(ApplyRet),
     Failed(ApplyRet),
+    // The test used more gas than its limit.
+    OutOfGas(ApplyRet),
+    // An expectation set by a cheatcode was not met, whatever the type of the test.
+    ExpectationFailed(String),
     Erred(String),
 }
 
+impl TestResultType {
+    /// Outcome of a test whose message was applied, passing if it failed as expected or
+    /// succeeded otherwise. Running out of a gas limit set on purpose, as reported by
+    /// [`KytheraExecutor::gas_limited`], is [`TestResultType::OutOfGas`] whatever the test
+    /// expects.
+    pub(crate) fn applied(apply_ret: ApplyRet, should_fail: bool, gas_limited: bool) -> Self {
+        let exit_code = apply_ret.msg_receipt.exit_code;
+        if gas_limited && exit_code == ExitCode::SYS_OUT_OF_GAS {
+            TestResultType::OutOfGas(apply_ret)
+        } else if exit_code.is_success() != should_fail {
+            TestResultType::Passed(apply_ret)
+        } else {
+            TestResultType::Failed(apply_ret)
+        }
+    }
+
+    /// Check if the test message was applied without meeting the expected exit code.
+    pub(crate) fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            TestResultType::Failed(_) | TestResultType::OutOfGas(_)
+        )
+    }
+
+    /// Outcome of a test whose message could not be applied,
+    /// [`TestResultType::ExpectationFailed`] if an expectation was not met.
+    pub(crate) fn erred(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<ExpectationFailure>() {
+            Some(failure) => TestResultType::ExpectationFailed(failure.to_string()),
+            None => TestResultType::Erred(err.to_string()),
+        }
+    }
+}
+
 /// Output of running a [`Method`] of an Actor test.
 #[derive(Clone, Debug)]
 pub struct TestResult {
     method: Method,
     ret: TestResultType,
+    fuzz: Option<FuzzReport>,
+    invariant: Option<InvariantReport>,
+    labels: HashMap<ActorID, String>,
+    state_root: Option<Cid>,
 }
 
 impl TestResult {
     /// Create a new [`TestResult`]
     pub fn new(method: Method, ret: TestResultType) -> Self {
-        TestResult { method, ret }
+        TestResult {
+            method,
+            ret,
+            fuzz: None,
+            invariant: None,
+            labels: HashMap::new(),
+            state_root: None,
+        }
     }
 
     /// Check if the [`TestResult`] passed.
@@ -160,7 +285,11 @@ impl fmt::Display for TestResult {
             let ok = ok.green();
             write!(f, "{ok}")
         } else {
-            let failed = "FAILED";
+            let failed = match self.ret {
+                TestResultType::OutOfGas(_) => "FAILED (out of gas)",
+                TestResultType::ExpectationFailed(_) => "FAILED (expectation not met)",
+                _ => "FAILED",
+            };
             #[cfg(feature = "colors")]
             let failed = failed.bright_red();
             write!(f, "{failed}")
@@ -178,6 +307,38 @@ impl TestResult {
     pub fn ret(&self) -> &TestResultType {
         &self.ret
     }
+
+    /// Get the events emitted during the test.
+    pub fn events(&self) -> &[StampedEvent] {
+        match &self.ret {
+            TestResultType::Passed(apply_ret)
+            | TestResultType::Failed(apply_ret)
+            | TestResultType::OutOfGas(apply_ret) => &apply_ret.events,
+            TestResultType::ExpectationFailed(_) | TestResultType::Erred(_) => &[],
+        }
+    }
+
+    /// Get the [`FuzzReport`] of the test if it is a `TestFuzz` method.
+    pub fn fuzz_report(&self) -> Option<&FuzzReport> {
+        self.fuzz.as_ref()
+    }
+
+    /// Get the [`InvariantReport`] of the test if it is an `Invariant` method.
+    pub fn invariant_report(&self) -> Option<&InvariantReport> {
+        self.invariant.as_ref()
+    }
+
+    /// Get the labels of the test accounts by actor ID, to be shown in place of their IDs.
+    pub fn labels(&self) -> &HashMap<ActorID, String> {
+        &self.labels
+    }
+
+    /// Get the state root after the test, if it was kept. Each test method runs on its own copy
+    /// of the state, which can be inspected with [`Tester::actor_state_after`] and
+    /// [`Tester::decode_state_after`]. It is not kept for `TestFuzz` and `Invariant` methods.
+    pub fn state_root(&self) -> Option<&Cid> {
+        self.state_root.as_ref()
+    }
 }
 
 /// Output of testing a list of Tests and its [`Method`]s for a target Actor.
@@ -187,6 +348,18 @@ pub struct TestActorResults<'a> {
     pub results: Result<Vec<TestResult>, Error>,
 }
 
+/// Progress of [`Tester::test_all`], streamed in the order of the test Actors and of their
+/// methods.
+#[derive(Clone, Debug)]
+pub enum TestEvent {
+    /// The tests of a test Actor start, with the number of its methods.
+    Started { name: String, tests: usize },
+    /// The test Actor that just started could not be deployed or set up.
+    SetupFailed(String),
+    /// A test method of the test Actor that just started finished.
+    Result(Box<TestResult>),
+}
+
 impl Tester {
     /// Create a new Kythera Tester.
     pub fn new() -> Self {
@@ -194,15 +367,121 @@ impl Tester {
 
         let builtin_actors = state_tree.load_builtin_actors();
         state_tree.load_kythera_actors();
-        let account = state_tree.create_account(*builtin_actors.manifest.get_account_code());
+        let account = state_tree
+            .create_account(
+                &builtin_actors.manifest,
+                &TestKey::Secp256k1(default_secret_key()),
+                TokenAmount::from_whole(TESTING_ACCOUNT_BALANCE),
+            )
+            .expect("Should be able to create the testing account");
+        let engine = KytheraExecutor::new_engine_pool(builtin_actors.root, 1);
 
         Self {
             builtin_actors,
+            engine,
             state_tree,
             account,
             target_actor: None,
+            fixtures: BTreeMap::new(),
             sequence: 0,
+            fuzz_config: FuzzConfig::default(),
+            invariant_config: InvariantConfig::default(),
+            gas_config: GasConfig::default(),
+            jobs: 1,
+            externs: FakeExterns::new(),
+            keyring: Keyring::new(),
+            registry: ActorRegistry::new(),
+            circ_supply_delta: TokenAmount::zero(),
+        }
+    }
+
+    /// Set the number of threads running the test methods, at least one.
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs.max(1);
+        self.engine = KytheraExecutor::new_engine_pool(
+            self.builtin_actors.root,
+            u32::try_from(self.jobs).unwrap_or(u32::MAX),
+        );
+    }
+
+    /// Set the [`FuzzConfig`] used to run `TestFuzz` methods.
+    pub fn set_fuzz_config(&mut self, fuzz_config: FuzzConfig) {
+        self.fuzz_config = fuzz_config;
+    }
+
+    /// Set the [`InvariantConfig`] used to run `Invariant` methods. The call sequences are
+    /// generated from the [`FuzzConfig`] seed.
+    pub fn set_invariant_config(&mut self, invariant_config: InvariantConfig) {
+        self.invariant_config = invariant_config;
+    }
+
+    /// Set the [`GasConfig`] of the messages executing the constructors and the test methods.
+    pub fn set_gas_config(&mut self, gas_config: GasConfig) {
+        self.gas_config = gas_config;
+    }
+
+    /// Set the [`RandomnessSource`] providing chain randomness when no `SetChainRandomness`
+    /// cheatcode applies.
+    pub fn set_chain_randomness(&mut self, randomness: RandomnessSource) {
+        self.externs = self.externs.clone().with_chain_randomness(randomness);
+    }
+
+    /// Set the [`RandomnessSource`] providing beacon randomness when no `SetBeaconRandomness`
+    /// cheatcode applies.
+    pub fn set_beacon_randomness(&mut self, randomness: RandomnessSource) {
+        self.externs = self.externs.clone().with_beacon_randomness(randomness);
+    }
+
+    /// Set the [`TipsetSource`] providing the tipset CIDs not set by the `SetTipsetCid` cheatcode.
+    pub fn set_tipset(&mut self, tipset: TipsetSource) {
+        self.externs = self.externs.clone().with_tipset(tipset);
+    }
+
+    /// Set the [`ConsensusFaultSource`] evaluating the consensus fault evidence not mocked by the
+    /// `MockConsensusFault` cheatcode.
+    pub fn set_consensus_fault(&mut self, fault: ConsensusFaultSource) {
+        self.externs = self.externs.clone().with_consensus_fault(fault);
+    }
+
+    /// Create an account with a key of a given type derived from a label, provided with a given
+    /// token balance. The account can be used by the cheatcodes, and its label is shown in
+    /// place of its ID in the test results.
+    pub fn create_account(
+        &mut self,
+        label: &str,
+        balance: TokenAmount,
+        key_type: KeyType,
+    ) -> Result<Account, Error> {
+        let key = TestKey::from_label(label, key_type);
+        let account =
+            self.state_tree
+                .create_account(&self.builtin_actors.manifest, &key, balance)?;
+
+        self.keyring.add_key(key);
+        self.keyring.set_label(account.0, label.to_string());
+        Ok(account)
+    }
+
+    /// Register an Actor so that it can be deployed from the tests with the `Deploy` cheatcode,
+    /// by name or by the returned CodeCID.
+    pub fn register_actor(&mut self, actor: &WasmActor) -> Result<Cid, Error> {
+        // Validate wasm bin.
+        if let Err(err) = validate_wasm_bin(actor.code()) {
+            return Err(Error::Tester {
+                msg: format!("Non valid actor wasm file: {}", actor.name()),
+                source: Some(Box::from(err)),
+            });
         }
+
+        let code = self.state_tree.put_actor_code(actor)?;
+        self.registry.register(
+            actor.name().to_string(),
+            RegisteredActor {
+                code,
+                constructor: actor.abi().constructor().map(Method::number),
+            },
+        );
+        Ok(code)
     }
 
     /// Retrieve the Deployed target Actor.
@@ -236,19 +515,33 @@ impl Tester {
         let root = self.state_tree.flush();
         let blockstore = self.state_tree.store().clone();
         let mut executor = KytheraExecutor::new(
+            self.engine.clone(),
             blockstore,
             root,
             self.builtin_actors.root,
             self.account.1,
-            address_id,
-        );
+            address_id.clone(),
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(self.circ_supply_delta.clone())
+        .with_gas_config(self.gas_config.clone());
 
         // Run the constructor if it exists.
         let ret = match actor.abi().constructor() {
             Some(constructor) => {
                 let sequence = self.state_tree.actor_sequence(self.account.0)?;
-
-                match executor.execute_method(address, constructor.number(), sequence) {
+                let (params, value) = actor.constructor_args(address_id);
+
+                match executor.execute_method_with_value(
+                    address,
+                    constructor.number(),
+                    params,
+                    value,
+                    sequence,
+                ) {
                     Ok(apply_ret) => {
                         if apply_ret.msg_receipt.exit_code != ExitCode::OK {
                             let source = apply_ret.failure_info.map(|f| f.to_string().into());
@@ -270,15 +563,408 @@ impl Tester {
             None => None,
         };
 
-        // Update owned state tree
+        // Update owned state tree, keyring and circulating supply
+        self.keyring = executor.keyring().clone();
+        self.circ_supply_delta = executor.circ_supply_delta().clone();
         let (root, blockstore) = executor.into_store();
         self.state_tree.override_inner(blockstore, root).unwrap();
 
         self.target_actor = Some(actor.deploy(address));
+        self.remove_fixture();
 
         Ok(ret)
     }
 
+    /// Deploy the fixture Actors of the target Actor into the `StateTree` and run their
+    /// constructors, replacing the previous fixture. Their IDs are read by name by the test
+    /// methods with the `Fixture` cheatcode. The `StateTree` is left untouched if any of them
+    /// fails to deploy.
+    /// Return the [`ApplyRet`]s of the constructor calls.
+    pub fn deploy_fixture(&mut self, actors: &[FixtureActor]) -> Result<Vec<ApplyRet>, Error> {
+        let root = self.state_tree.flush();
+        let fixtures = self.fixtures.clone();
+
+        let res = self.deploy_fixture_actors(actors);
+        if res.is_err() {
+            let blockstore = self.state_tree.store().clone();
+            self.state_tree.override_inner(blockstore, root)?;
+            self.fixtures = fixtures;
+        }
+        res
+    }
+
+    /// Deploy the fixture Actors, after removing the Actors of the previous fixture.
+    fn deploy_fixture_actors(&mut self, actors: &[FixtureActor]) -> Result<Vec<ApplyRet>, Error> {
+        let target = self.target_actor.as_ref().ok_or(Error::MissingActor {
+            msg: "Main Actor not loaded".to_string(),
+        })?;
+        let target_id = RawBytes::new(
+            to_vec(&target.address.id().expect("Actor Id should be valid"))
+                .expect("Should be able to serialize target actor ID"),
+        );
+
+        self.remove_fixture();
+
+        // Set actors bin.
+        let mut fixtures = BTreeMap::new();
+        let mut addresses = vec![];
+        for fixture in actors {
+            if let Err(err) = validate_wasm_bin(fixture.actor.code()) {
+                return Err(Error::Tester {
+                    msg: format!(
+                        "Non valid fixture actor wasm file: {}",
+                        fixture.actor.name()
+                    ),
+                    source: Some(Box::from(err)),
+                });
+            }
+
+            let address = match fixture.id {
+                Some(id) => self.state_tree.deploy_actor_from_bin_at_id(
+                    id,
+                    &fixture.actor,
+                    fixture.balance.clone(),
+                )?,
+                None => self
+                    .state_tree
+                    .deploy_actor_from_bin(&fixture.actor, fixture.balance.clone())?,
+            };
+            fixtures.insert(
+                fixture.name.clone(),
+                address.id().expect("Actor Id should be valid"),
+            );
+            addresses.push(address);
+        }
+
+        // Instantiate executor.
+        let root = self.state_tree.flush();
+        let blockstore = self.state_tree.store().clone();
+        let mut executor = KytheraExecutor::new(
+            self.engine.clone(),
+            blockstore,
+            root,
+            self.builtin_actors.root,
+            self.account.1,
+            target_id,
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(self.circ_supply_delta.clone())
+        .with_gas_config(self.gas_config.clone());
+
+        // Run the constructors that exist.
+        let mut rets = vec![];
+        let mut sequence = self.state_tree.actor_sequence(self.account.0)?;
+        for (fixture, address) in actors.iter().zip(addresses) {
+            let constructor = match fixture.actor.abi().constructor() {
+                Some(constructor) => constructor,
+                None => continue,
+            };
+
+            match executor.execute_method_with_params(
+                address,
+                constructor.number(),
+                fixture.constructor_params.clone(),
+                sequence,
+            ) {
+                Ok(apply_ret) => {
+                    if apply_ret.msg_receipt.exit_code != ExitCode::OK {
+                        let source = apply_ret.failure_info.map(|f| f.to_string().into());
+                        return Err(Error::Constructor {
+                            name: fixture.name.clone(),
+                            source,
+                        });
+                    }
+                    rets.push(apply_ret);
+                }
+                Err(err) => {
+                    return Err(Error::Constructor {
+                        name: fixture.name.clone(),
+                        source: Some(err.into()),
+                    });
+                }
+            }
+            sequence += 1;
+        }
+
+        // Update owned state tree, keyring and circulating supply
+        self.keyring = executor.keyring().clone();
+        self.circ_supply_delta = executor.circ_supply_delta().clone();
+        let (root, blockstore) = executor.into_store();
+        self.state_tree.override_inner(blockstore, root).unwrap();
+
+        self.fixtures = fixtures;
+
+        Ok(rets)
+    }
+
+    /// Remove the Actors of the fixture from the `StateTree`.
+    fn remove_fixture(&mut self) {
+        for id in self.fixtures.values() {
+            self.state_tree.delete_actor(*id);
+        }
+        self.fixtures.clear();
+    }
+
+    /// Retrieve the IDs of the fixture Actors by name.
+    pub fn fixtures(&self) -> &BTreeMap<String, ActorID> {
+        &self.fixtures
+    }
+
+    /// Get the ID and address of the testing account, sending the messages of the tests.
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Send a message from an account to an Actor with the given parameters and value, keeping
+    /// the resulting state for the next calls and tests. The message is run with the configured
+    /// gas limit if none is given.
+    /// Return the [`CallResult`] of the message, whose balance changes can be checked against
+    /// the value sent.
+    pub fn call(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        method: impl Into<MethodId>,
+        params: RawBytes,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+    ) -> Result<CallResult, Error> {
+        let method = method.into().num()?;
+        self.send(
+            from,
+            to,
+            method,
+            value.clone(),
+            gas_limit,
+            |executor, sequence| {
+                executor.execute_method_with_value(*to, method, params, value, sequence)
+            },
+        )
+    }
+
+    /// Send a message from an account to an Actor, applied by `execute` with the executor and
+    /// the sequence of the sender, and keep its resulting state.
+    fn send(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        method: MethodNum,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        execute: impl FnOnce(&mut KytheraExecutor, u64) -> anyhow::Result<ApplyRet>,
+    ) -> Result<CallResult, Error> {
+        let from_id = match self.state_tree.actor_id(from)? {
+            Some(actor_id) => actor_id,
+            None => {
+                return Err(Error::Tester {
+                    msg: format!("Could not find sender Actor {from}"),
+                    source: None,
+                })
+            }
+        };
+        let gas_config = GasConfig {
+            gas_limit: gas_limit.or(self.gas_config.gas_limit),
+            ..self.gas_config.clone()
+        };
+
+        // Instantiate executor.
+        let previous_root = self.state_tree.flush();
+        let blockstore = self.state_tree.store().clone();
+        let mut executor = KytheraExecutor::new(
+            self.engine.clone(),
+            blockstore,
+            previous_root,
+            self.builtin_actors.root,
+            *from,
+            RawBytes::default(),
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(self.circ_supply_delta.clone())
+        .with_gas_config(gas_config);
+
+        let sequence = self.state_tree.actor_sequence(from_id)?;
+        let ret = execute(&mut executor, sequence).map_err(|err| Error::Tester {
+            msg: format!("Could not call method {method} of Actor {to}: {err}"),
+            source: None,
+        })?;
+
+        // Update owned state tree, keyring and circulating supply
+        self.keyring = executor.keyring().clone();
+        self.circ_supply_delta = executor.circ_supply_delta().clone();
+        let (root, blockstore) = executor.into_store();
+        self.state_tree.override_inner(blockstore, root)?;
+
+        // Compare the balances of the called Actors before and after the message.
+        let mut balance_changes = BTreeMap::new();
+        for address in std::iter::once(*to).chain(call::called_addresses(&ret)) {
+            let actor_id = match self.state_tree.actor_id(&address)? {
+                Some(actor_id) if actor_id != from_id => actor_id,
+                _ => continue,
+            };
+            let change = self.state_tree.actor_balance_at(&root, actor_id)?
+                - self.state_tree.actor_balance_at(&previous_root, actor_id)?
+                - CallResult::fees_received(&ret, actor_id);
+            balance_changes.insert(actor_id, change);
+        }
+
+        Ok(CallResult {
+            ret,
+            value,
+            balance_changes,
+        })
+    }
+
+    /// Send a message like [`Tester::call`], with parameters encoded in CBOR.
+    pub fn call_cbor<P: Serialize>(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        method: impl Into<MethodId>,
+        params: &P,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+    ) -> Result<CallResult, Error> {
+        let params = RawBytes::serialize(params).map_err(|err| Error::Tester {
+            msg: format!("Could not serialize parameters: {err}"),
+            source: None,
+        })?;
+        self.call(from, to, method, params, value, gas_limit)
+    }
+
+    /// Send a message like [`Tester::call`], discarding the resulting state so that the next
+    /// calls and tests are not affected by it. The message is executed as usual, so it can
+    /// transfer value and write state, only to be rolled back once it is applied.
+    pub fn dry_run(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        method: impl Into<MethodId>,
+        params: RawBytes,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+    ) -> Result<CallResult, Error> {
+        self.rolled_back(|tester| tester.call(from, to, method, params, value, gas_limit))
+    }
+
+    /// Send a message from an account to an Actor as a read-only call, in which the Actors can
+    /// neither write their state, transfer value nor create Actors: such a message aborts
+    /// instead. No value is sent with it, and its resulting state is discarded like in
+    /// [`Tester::dry_run`].
+    pub fn call_readonly(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        method: impl Into<MethodId>,
+        params: RawBytes,
+        gas_limit: Option<u64>,
+    ) -> Result<CallResult, Error> {
+        let method = method.into().num()?;
+        self.rolled_back(|tester| {
+            tester.send(
+                from,
+                to,
+                method,
+                TokenAmount::zero(),
+                gas_limit,
+                |executor, sequence| {
+                    executor.execute_method_read_only(*to, method, params, sequence)
+                },
+            )
+        })
+    }
+
+    /// Run `f` on the [`Tester`], then restore its state tree, keyring and circulating supply,
+    /// the blockstore keeping the blocks written meanwhile.
+    fn rolled_back<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let root = self.state_tree.flush();
+        let keyring = self.keyring.clone();
+        let circ_supply_delta = self.circ_supply_delta.clone();
+        let result = f(self);
+
+        self.keyring = keyring;
+        self.circ_supply_delta = circ_supply_delta;
+        let blockstore = self.state_tree.store().clone();
+        self.state_tree.override_inner(blockstore, root)?;
+
+        result
+    }
+
+    /// Retrieve the state of an Actor, holding its code, balance and state root.
+    pub fn actor_state(&self, address: &Address) -> Result<ActorState, Error> {
+        let actor_state = match self.state_tree.actor_id(address)? {
+            Some(actor_id) => self.state_tree.actor_state(actor_id)?,
+            None => None,
+        };
+        actor_state.ok_or_else(|| Error::MissingActor {
+            msg: format!("Missing actor in state tree: {address}"),
+        })
+    }
+
+    /// Decode the CBOR encoded state of an Actor from its state root.
+    pub fn decode_state<T: DeserializeOwned>(&self, address: &Address) -> Result<T, Error> {
+        let actor_state = self.actor_state(address)?;
+        self.state_tree.decode_block(&actor_state.state)
+    }
+
+    /// Retrieve the state of an Actor after a test method, as the methods run on their own copy
+    /// of the state and don't change the state of the [`Tester`].
+    pub fn actor_state_after(
+        &self,
+        result: &TestResult,
+        address: &Address,
+    ) -> Result<ActorState, Error> {
+        let root = result.state_root().ok_or_else(|| Error::StateTree {
+            msg: format!("No state kept after test {}", result.method().name()),
+        })?;
+        self.state_tree
+            .actor_state_at(root, address)?
+            .ok_or_else(|| Error::MissingActor {
+                msg: format!("Missing actor in state tree: {address}"),
+            })
+    }
+
+    /// Decode the CBOR encoded state of an Actor after a test method.
+    pub fn decode_state_after<T: DeserializeOwned>(
+        &self,
+        result: &TestResult,
+        address: &Address,
+    ) -> Result<T, Error> {
+        let actor_state = self.actor_state_after(result, address)?;
+        self.state_tree.decode_block(&actor_state.state)
+    }
+
+    /// Decode a CBOR block of the state, such as an object linked from an Actor state.
+    pub fn decode_block<T: DeserializeOwned>(&self, cid: &Cid) -> Result<T, Error> {
+        self.state_tree.decode_block(cid)
+    }
+
+    /// Retrieve the entries of a HAMT of the state by key bytes. Most Actors build their HAMTs
+    /// with a bit width of [`HAMT_BIT_WIDTH`].
+    pub fn hamt_entries<V: Serialize + DeserializeOwned + Clone>(
+        &self,
+        root: &Cid,
+        bit_width: u32,
+    ) -> Result<BTreeMap<Vec<u8>, V>, Error> {
+        self.state_tree.hamt_entries(root, bit_width)
+    }
+
+    /// Retrieve the entries of an AMT of the state by index.
+    pub fn amt_entries<V: Serialize + DeserializeOwned + Clone>(
+        &self,
+        root: &Cid,
+    ) -> Result<BTreeMap<u64, V>, Error> {
+        self.state_tree.amt_entries(root)
+    }
+
     // Get and increment the next Actor sequence.
     pub fn next_sequence(&mut self) -> u64 {
         let sequence = self.sequence;
@@ -290,8 +976,22 @@ impl Tester {
     pub fn test(
         &mut self,
         test_actor: &WasmActor,
-        stream_results: Option<SyncSender<(WasmActor, TestResult)>>,
+        stream_results: Option<Sender<TestEvent>>,
     ) -> Result<Vec<TestResult>, Error> {
+        self.test_all(&[test_actor], stream_results)?
+            .pop()
+            .expect("Should have the results of the test actor")
+            .results
+    }
+
+    /// Test a list of Actors on a `MemoryBlockstore`, running their methods on the number of
+    /// threads set with [`Tester::set_jobs`]. Progress is streamed in the order of the test
+    /// Actors and of their methods.
+    pub fn test_all<'a>(
+        &mut self,
+        test_actors: &[&'a WasmActor],
+        stream_results: Option<Sender<TestEvent>>,
+    ) -> Result<Vec<TestActorResults<'a>>, Error> {
         // Get target actor Id to pass it to test methods.
         let target = self
             .target_actor
@@ -300,19 +1000,59 @@ impl Tester {
             .ok_or(Error::MissingActor {
                 msg: "Main Actor not loaded".to_string(),
             })?;
+        let target_actor_id = target.address.id().expect("Actor Id should be valid");
 
-        let target_id = match target.address.id() {
-            Ok(id) => {
-                RawBytes::new(to_vec(&id).expect("Should be able to serialize target actor ID"))
-            }
-            Err(_) => panic!("Actor Id should be valid"),
+        // Deploy the test actors one after the other so that their addresses don't depend on
+        // the number of jobs.
+        let prepared = test_actors
+            .iter()
+            .map(|test_actor| self.prepare_test(target_actor_id, test_actor))
+            .collect::<Vec<Result<PreparedTest, Error>>>();
+
+        let runner = Runner {
+            engine: &self.engine,
+            builtin_actors: self.builtin_actors.root,
+            account_address: self.account.1,
+            target: &target,
+            target_actor_id,
+            blockstore: Mutex::new(self.state_tree.store().clone()),
+            fuzz_config: self.fuzz_config,
+            invariant_config: self.invariant_config,
+            externs: &self.externs,
+            keyring: &self.keyring,
+            registry: &self.registry,
+            fixtures: &self.fixtures,
+            gas_config: &self.gas_config,
         };
+        let results = runner.run(test_actors, &prepared, self.jobs, stream_results.as_ref());
 
-        // Iterate over all test actors
-        log::info!(
-            "{}: testing {} tests",
-            test_actor.name(),
-            test_actor.abi().methods().len()
+        // Keep the blocks of the states after the test methods so that they can be inspected.
+        let blockstore = runner
+            .blockstore
+            .into_inner()
+            .expect("Should be able to take the blockstore");
+        let root = self.state_tree.flush();
+        self.state_tree.override_inner(blockstore, root)?;
+
+        Ok(test_actors
+            .iter()
+            .zip(prepared)
+            .zip(results)
+            .map(|((test_actor, prepared), results)| TestActorResults {
+                test_actor,
+                results: prepared.map(|_| results),
+            })
+            .collect())
+    }
+
+    /// Deploy a test Actor and run its `Constructor` and `Setup` methods.
+    fn prepare_test<'a>(
+        &mut self,
+        target_actor_id: ActorID,
+        test_actor: &'a WasmActor,
+    ) -> Result<PreparedTest<'a>, Error> {
+        let target_id = RawBytes::new(
+            to_vec(&target_actor_id).expect("Should be able to serialize target actor ID"),
         );
 
         // Validate actor bin.
@@ -324,32 +1064,40 @@ impl Tester {
         }
 
         // Deploy test actor
-        let test_address = match self
+        let test_address = self
             .state_tree
-            .deploy_actor_from_bin(test_actor, TokenAmount::zero())
-        {
-            // Properly deployed, get address
-            Ok(test_address) => test_address,
-            // Error on deployment, return error as part of [`TestResults`]
-            Err(err) => return Err(err),
-        };
+            .deploy_actor_from_bin(test_actor, TokenAmount::zero())?;
 
         // Instantiate executor.
         let root = self.state_tree.flush();
         let blockstore = self.state_tree.store().clone();
         let mut executor = KytheraExecutor::new(
+            self.engine.clone(),
             blockstore,
             root,
             self.builtin_actors.root,
             self.account.1,
             target_id.clone(),
-        );
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(self.circ_supply_delta.clone())
+        .with_gas_config(self.gas_config.clone());
 
         let mut sequence = self.state_tree.actor_sequence(self.account.0)?;
 
         // Run the constructor if it exists.
         if let Some(constructor) = test_actor.abi().constructor() {
-            match executor.execute_method(test_address, constructor.number(), sequence) {
+            let (params, value) = test_actor.constructor_args(target_id);
+            match executor.execute_method_with_value(
+                test_address,
+                constructor.number(),
+                params,
+                value,
+                sequence,
+            ) {
                 Ok(apply_ret) => {
                     if apply_ret.msg_receipt.exit_code != ExitCode::OK {
                         let source = apply_ret.failure_info.map(|f| f.to_string().into());
@@ -390,70 +1138,22 @@ impl Tester {
             }
         }
 
-        // Update owned state tree
+        // Update owned state tree, keyring and circulating supply
+        self.keyring = executor.keyring().clone();
+        self.circ_supply_delta = executor.circ_supply_delta().clone();
         let (root, blockstore) = executor.into_store();
         self.state_tree.override_inner(blockstore, root).unwrap();
 
         // Increment the sequence for the methods tests.
         let sequence = self.state_tree.actor_sequence(self.account.0)?;
 
-        // TODO concurrent testing
-        // We'll be able to use thread to do concurrent testing once we set the Engine Pool with more than
-        // one possible concurrent engine.
-        // The following steps will not end up in a result. Either we could finalize message
-        // handling and we return the related ApplyRet or we return nothing.
-        Ok(test_actor
-            .abi
-            .methods
-            .iter()
-            .map(|method| {
-                let root = self.state_tree.flush();
-                let blockstore = self.state_tree.store().clone();
-                // TODO is it possible to impl `Clone` for `DefaultExecutor`
-                // and submit PR upstream to implement with it?
-                let mut executor = KytheraExecutor::new(
-                    blockstore,
-                    root,
-                    self.builtin_actors.root,
-                    self.account.1,
-                    target_id.clone(),
-                );
-
-                log::debug!(
-                    "Testing test {}.{}() for Actor {}",
-                    test_actor.name,
-                    method.name(),
-                    target.name
-                );
-                let message = executor.execute_method(test_address, method.number(), sequence);
-
-                let ret = match message {
-                    Ok(apply_ret) => match (method.r#type(), apply_ret.msg_receipt.exit_code) {
-                        (MethodType::Test, ExitCode::OK) => TestResultType::Passed(apply_ret),
-                        (MethodType::TestFail, exit_code) => {
-                            if exit_code == ExitCode::OK {
-                                TestResultType::Failed(apply_ret)
-                            } else {
-                                TestResultType::Passed(apply_ret)
-                            }
-                        }
-                        _ => TestResultType::Failed(apply_ret),
-                    },
-                    Err(err) => TestResultType::Erred(err.to_string()),
-                };
-
-                let result = TestResult {
-                    method: method.clone(),
-                    ret,
-                };
-                if let Some(ref sender) = stream_results {
-                    if let Err(err) = sender.send((test_actor.clone(), result.clone())) {
-                        log::error!("Could not Stream the Result: {err}");
-                    }
-                }
-                result
-            })
-            .collect())
+        Ok(PreparedTest {
+            test_actor,
+            test_address,
+            root: self.state_tree.flush(),
+            circ_supply_delta: self.circ_supply_delta.clone(),
+            sequence,
+        })
     }
 }
 
@@ -467,6 +1167,7 @@ impl Default for Tester {
 mod tests {
     use super::*;
     use fvm_ipld_blockstore::Blockstore;
+    use fvm_shared::address::Protocol;
 
     #[test]
     fn test_tester_instantiation() {
@@ -541,4 +1242,413 @@ mod tests {
 
         assert!(tester.target_actor.is_none());
     }
+
+    #[test]
+    fn test_applied_result_type() {
+        let ret = |exit_code| ApplyRet::prevalidation_fail(exit_code, "", TokenAmount::zero());
+
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::OK), false, false),
+            TestResultType::Passed(_)
+        ));
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::USR_ASSERTION_FAILED), true, false),
+            TestResultType::Passed(_)
+        ));
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::OK), true, true),
+            TestResultType::Failed(_)
+        ));
+        // Running out of the default gas limit is a regular failure, that `TestFail` methods
+        // expect.
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::SYS_OUT_OF_GAS), false, false),
+            TestResultType::Failed(_)
+        ));
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::SYS_OUT_OF_GAS), true, false),
+            TestResultType::Passed(_)
+        ));
+        // Running out of a gas limit set on purpose fails the test whatever its type.
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::SYS_OUT_OF_GAS), false, true),
+            TestResultType::OutOfGas(_)
+        ));
+        assert!(matches!(
+            TestResultType::applied(ret(ExitCode::SYS_OUT_OF_GAS), true, true),
+            TestResultType::OutOfGas(_)
+        ));
+    }
+
+    #[test]
+    fn test_tester_create_account() {
+        let mut tester = Tester::new();
+
+        let alice = tester
+            .create_account("alice", TokenAmount::from_atto(100), KeyType::Secp256k1)
+            .unwrap();
+        let bob = tester
+            .create_account("bob", TokenAmount::from_atto(200), KeyType::Bls)
+            .unwrap();
+        let carol = tester
+            .create_account("carol", TokenAmount::zero(), KeyType::Delegated)
+            .unwrap();
+
+        assert_eq!(alice.1.protocol(), Protocol::Secp256k1);
+        assert_eq!(bob.1.protocol(), Protocol::BLS);
+        assert_eq!(carol.1.protocol(), Protocol::Delegated);
+        assert_eq!(tester.keyring.label(alice.0), Some("alice"));
+        assert_eq!(tester.keyring.label(bob.0), Some("bob"));
+        assert_eq!(tester.keyring.label(carol.0), Some("carol"));
+        assert!(tester.keyring.key(&bob.1).is_some());
+
+        // Labels derive the keys, so they can only be used once per key type.
+        assert!(tester
+            .create_account("alice", TokenAmount::zero(), KeyType::Secp256k1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tester_call() {
+        let mut tester = Tester::new();
+        let from = tester.account().1;
+        let alice = tester
+            .create_account("alice", TokenAmount::zero(), KeyType::Secp256k1)
+            .unwrap();
+
+        // Plain value transfers are sent with method 0.
+        let result = tester
+            .call(
+                &from,
+                &alice.1,
+                0,
+                RawBytes::default(),
+                TokenAmount::from_atto(100),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+        assert_eq!(
+            result.balance_changes,
+            BTreeMap::from([(alice.0, TokenAmount::from_atto(100))])
+        );
+        assert!(result.check_balance_changes().is_ok());
+
+        // The state is kept for the next calls.
+        let result = tester
+            .call(
+                &from,
+                &alice.1,
+                0,
+                RawBytes::default(),
+                TokenAmount::from_atto(50),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            result.balance_changes,
+            BTreeMap::from([(alice.0, TokenAmount::from_atto(50))])
+        );
+        assert!(result.check_balance_changes().is_ok());
+        let root = tester.state_tree.flush();
+        assert_eq!(
+            tester.state_tree.actor_balance_at(&root, alice.0).unwrap(),
+            TokenAmount::from_atto(150)
+        );
+
+        // Messages can be sent from any account.
+        let bob = tester
+            .create_account("bob", TokenAmount::zero(), KeyType::Bls)
+            .unwrap();
+        let result = tester
+            .call(
+                &alice.1,
+                &bob.1,
+                0,
+                RawBytes::default(),
+                TokenAmount::from_atto(50),
+                Some(10_000_000),
+            )
+            .unwrap();
+        assert_eq!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+        assert_eq!(
+            result.balance_changes,
+            BTreeMap::from([(bob.0, TokenAmount::from_atto(50))])
+        );
+        assert!(result.check_balance_changes().is_ok());
+        let root = tester.state_tree.flush();
+        assert_eq!(
+            tester.state_tree.actor_balance_at(&root, alice.0).unwrap(),
+            TokenAmount::from_atto(100)
+        );
+        assert_eq!(
+            tester.state_tree.actor_balance_at(&root, bob.0).unwrap(),
+            TokenAmount::from_atto(50)
+        );
+
+        // Messages can only be sent from existing accounts.
+        assert!(tester
+            .call(
+                &Address::new_id(9999),
+                &alice.1,
+                0,
+                RawBytes::default(),
+                TokenAmount::zero(),
+                None,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_tester_dry_run() {
+        let mut tester = Tester::new();
+        let from = tester.account().1;
+        let alice = tester
+            .create_account("alice", TokenAmount::zero(), KeyType::Secp256k1)
+            .unwrap();
+
+        let root = tester.state_tree.flush();
+        let result = tester
+            .dry_run(
+                &from,
+                &alice.1,
+                0,
+                RawBytes::default(),
+                TokenAmount::from_atto(100),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+        assert_eq!(
+            result.balance_changes,
+            BTreeMap::from([(alice.0, TokenAmount::from_atto(100))])
+        );
+
+        // The state is rolled back.
+        assert_eq!(tester.state_tree.flush(), root);
+        assert_eq!(
+            tester.state_tree.actor_balance_at(&root, alice.0).unwrap(),
+            TokenAmount::zero()
+        );
+    }
+
+    #[test]
+    fn test_tester_call_readonly() {
+        let mut tester = Tester::new();
+        let from = tester.account().1;
+        let alice = tester
+            .create_account("alice", TokenAmount::zero(), KeyType::Secp256k1)
+            .unwrap();
+
+        let root = tester.state_tree.flush();
+        let result = tester
+            .call_readonly(&from, &alice.1, 0, RawBytes::default(), None)
+            .unwrap();
+        assert_eq!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+
+        // Sending to a new address would create its account, which aborts in a read-only call.
+        let bob = Address::new_secp256k1(&[1; 65]).unwrap();
+        let result = tester
+            .call_readonly(&from, &bob, 0, RawBytes::default(), None)
+            .unwrap();
+        assert_ne!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+        assert_eq!(tester.state_tree.flush(), root);
+        assert!(tester.state_tree.actor_id(&bob).unwrap().is_none());
+
+        // The same message succeeds when it isn't read-only.
+        let result = tester
+            .dry_run(
+                &from,
+                &bob,
+                0,
+                RawBytes::default(),
+                TokenAmount::zero(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.ret.msg_receipt.exit_code, ExitCode::OK);
+    }
+
+    #[test]
+    fn test_tester_actor_state() {
+        let mut tester = Tester::new();
+        let alice = tester
+            .create_account("alice", TokenAmount::from_atto(100), KeyType::Secp256k1)
+            .unwrap();
+
+        let actor_state = tester.actor_state(&alice.1).unwrap();
+        assert_eq!(actor_state.balance, TokenAmount::from_atto(100));
+        assert_eq!(
+            &actor_state.code,
+            tester.builtin_actors.manifest.get_account_code()
+        );
+        let state: fil_actor_account_v10::State = tester.decode_state(&alice.1).unwrap();
+        assert_eq!(state.address, alice.1);
+        assert!(tester.decode_state::<u64>(&alice.1).is_err());
+        assert!(tester.actor_state(&Address::new_id(9999)).is_err());
+    }
+
+    #[test]
+    fn test_tester_hamt_amt_entries() {
+        let tester = Tester::new();
+        let store = tester.state_tree.store();
+
+        let mut hamt = fvm_ipld_hamt::Hamt::<_, u64>::new_with_bit_width(store, HAMT_BIT_WIDTH);
+        hamt.set(b"alice".to_vec().into(), 1).unwrap();
+        hamt.set(b"bob".to_vec().into(), 2).unwrap();
+        let hamt_root = hamt.flush().unwrap();
+        assert_eq!(
+            tester
+                .hamt_entries::<u64>(&hamt_root, HAMT_BIT_WIDTH)
+                .unwrap(),
+            BTreeMap::from([(b"alice".to_vec(), 1), (b"bob".to_vec(), 2)])
+        );
+
+        let mut amt = fvm_ipld_amt::Amt::<String, _>::new(store);
+        amt.set(0, String::from("first")).unwrap();
+        amt.set(10, String::from("second")).unwrap();
+        let amt_root = amt.flush().unwrap();
+        assert_eq!(
+            tester.amt_entries::<String>(&amt_root).unwrap(),
+            BTreeMap::from([(0, String::from("first")), (10, String::from("second"))])
+        );
+        assert!(tester.decode_block::<Vec<u8>>(&amt_root).is_err());
+    }
+
+    #[test]
+    fn test_tester_register_actor() {
+        let mut tester = Tester::new();
+
+        let actor = WasmActor::new(
+            String::from("Target.wasm"),
+            Vec::from(kythera_actors::wasm_bin::test_actors::BASIC_TARGET_ACTOR_BINARY),
+            Abi {
+                constructor: Some(Method::new_from_name("Constructor").unwrap()),
+                set_up: None,
+                methods: vec![],
+            },
+        );
+        let code = tester.register_actor(&actor).unwrap();
+
+        // The code is available to the executors and the actor can be found by name and code.
+        assert!(tester.state_tree.store().has(&code).unwrap());
+        let registered = tester.registry.get("Target.wasm").unwrap();
+        assert_eq!(registered.code, code);
+        assert_eq!(
+            registered.constructor,
+            Some(Method::new_from_name("Constructor").unwrap().number())
+        );
+        assert_eq!(tester.registry.get_by_code(&code), Some(registered));
+
+        // Non valid wasm files are not registered.
+        assert!(tester
+            .register_actor(&WasmActor::new(
+                String::from("Other.wasm"),
+                vec![1, 2, 3],
+                Abi::default()
+            ))
+            .is_err());
+        assert!(tester.registry.get("Other.wasm").is_none());
+    }
+
+    #[test]
+    fn test_tester_deploy_fixture() {
+        let mut tester = Tester::new();
+        let actor = |name: &str| {
+            WasmActor::new(
+                String::from(name),
+                Vec::from(kythera_actors::wasm_bin::test_actors::BASIC_TARGET_ACTOR_BINARY),
+                Abi::default(),
+            )
+        };
+        let fixture = vec![
+            FixtureActor {
+                name: String::from("Token"),
+                actor: actor("Token.wasm"),
+                constructor_params: RawBytes::default(),
+                balance: TokenAmount::from_atto(100),
+                id: Some(500),
+            },
+            FixtureActor {
+                name: String::from("Market"),
+                actor: actor("Market.wasm"),
+                constructor_params: RawBytes::default(),
+                balance: TokenAmount::zero(),
+                id: None,
+            },
+        ];
+
+        // Fixtures are deployed along with a target actor.
+        assert!(tester.deploy_fixture(&fixture).is_err());
+
+        tester.deploy_target_actor(actor("Target.wasm")).unwrap();
+        tester.deploy_fixture(&fixture).unwrap();
+
+        // The IDs assigned after a fixed one start after it.
+        assert_eq!(tester.fixtures().get("Token"), Some(&500));
+        assert_eq!(tester.fixtures().get("Market"), Some(&501));
+        assert!(tester.state_tree.actor_sequence(500).is_ok());
+
+        // Replacing the fixture removes its actors, freeing its fixed IDs.
+        tester.deploy_fixture(&fixture).unwrap();
+        assert_eq!(tester.fixtures().get("Token"), Some(&500));
+        assert_eq!(tester.fixtures().get("Market"), Some(&502));
+        assert!(tester.state_tree.actor_state(501).unwrap().is_none());
+
+        // A fixture deploying only partly leaves the state and the previous fixture untouched.
+        let target_id = tester.deployed_actor().unwrap().address().id().unwrap();
+        let clashing = vec![
+            FixtureActor {
+                id: None,
+                ..fixture[1].clone()
+            },
+            FixtureActor {
+                id: Some(target_id),
+                ..fixture[0].clone()
+            },
+        ];
+        assert!(tester.deploy_fixture(&clashing).is_err());
+        assert_eq!(tester.fixtures().get("Market"), Some(&502));
+        assert!(tester.state_tree.actor_state(500).unwrap().is_some());
+        assert!(tester.state_tree.actor_state(503).unwrap().is_none());
+
+        // Deploying a target actor removes the fixture.
+        tester.deploy_target_actor(actor("Other.wasm")).unwrap();
+        assert!(tester.fixtures().is_empty());
+        assert!(tester.state_tree.actor_state(500).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_constructor_args() {
+        let actor = WasmActor::new(String::from("Target.wasm"), vec![], Abi::default());
+        let target_id = RawBytes::new(to_vec(&103u64).unwrap());
+
+        // The target Actor ID is sent when no parameters were set.
+        let (params, value) = actor.constructor_args(target_id.clone());
+        assert_eq!(params, target_id);
+        assert!(value.is_zero());
+
+        let actor = actor.with_constructor_params(
+            RawBytes::new(to_vec(&(1u64, "Kythera")).unwrap()),
+            TokenAmount::from_whole(1),
+        );
+        let (params, value) = actor.constructor_args(target_id);
+        let (number, name): (u64, String) = from_slice(params.bytes()).unwrap();
+        assert_eq!((number, name.as_str()), (1, "Kythera"));
+        assert_eq!(value, TokenAmount::from_whole(1));
+    }
+
+    #[test]
+    fn test_result_type_erred() {
+        // Expectations not met are told apart from the other errors of the message.
+        let ret = TestResultType::erred(anyhow::Error::new(ExpectationFailure(String::from(
+            "Call to f0101 (method 2) did not revert, expected exit code 16",
+        ))));
+        assert!(
+            matches!(ret, TestResultType::ExpectationFailed(ref failure) if failure.contains("did not revert"))
+        );
+
+        let ret = TestResultType::erred(anyhow::anyhow!("Could not apply message"));
+        assert!(matches!(ret, TestResultType::Erred(ref err) if err == "Could not apply message"));
+    }
 }
@@ -0,0 +1,475 @@
+// Copyright 2023 Polyphene.
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::BTreeMap;
+use std::error::Error as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+use cid::Cid;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::{RawBytes, DAG_CBOR};
+use fvm_shared::ActorID;
+use kythera_common::abi::{Method, MethodType};
+use kythera_fvm::{
+    engine::EnginePool,
+    executor::{GasConfig, KytheraExecutor},
+    externs::FakeExterns,
+    keys::Keyring,
+    registry::ActorRegistry,
+    Address, TokenAmount,
+};
+use libipld_core::ipld::Ipld;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::error::Error;
+use crate::fuzz::{self, FuzzConfig, FuzzReport, FuzzValue};
+use crate::invariant::{InvariantCampaign, InvariantConfig};
+use crate::{DeployedActor, TestEvent, TestResult, TestResultType, WasmActor};
+
+/// A test Actor deployed with its `Constructor` and `Setup` run, ready for its methods to be
+/// tested.
+pub(crate) struct PreparedTest<'a> {
+    pub test_actor: &'a WasmActor,
+    pub test_address: Address,
+    // State root after the `Setup` of the test Actor.
+    pub root: Cid,
+    // Tokens added to the circulating supply by the cheatcodes until the `Setup`.
+    pub circ_supply_delta: TokenAmount,
+    // Account sequence for the test methods.
+    pub sequence: u64,
+}
+
+/// A unit of work executed by one of the [`Runner`] threads.
+enum Job {
+    // Run a single method of a test Actor.
+    Method { test: usize, method: usize },
+    // Run the call sequences checking all the `Invariant` methods of a test Actor.
+    Invariants { test: usize, methods: Vec<usize> },
+}
+
+/// Progress of the [`TestEvent`]s streamed by a [`Runner`].
+struct Stream<'s> {
+    sender: Option<&'s Sender<TestEvent>>,
+    // Number of test Actors that have been started.
+    started: usize,
+    // Number of test results that have been streamed.
+    streamed: usize,
+}
+
+impl Stream<'_> {
+    /// Stream the results that are available in order, starting the next test Actors as soon as
+    /// all the results of the previous ones have been streamed.
+    fn advance(
+        &mut self,
+        test_actors: &[&WasmActor],
+        tests: &[Result<PreparedTest, Error>],
+        offsets: &[usize],
+        results: &[Option<TestResult>],
+    ) {
+        loop {
+            while self.streamed < offsets[self.started] {
+                match &results[self.streamed] {
+                    Some(result) => self.send(TestEvent::Result(Box::new(result.clone()))),
+                    None => return,
+                }
+                self.streamed += 1;
+            }
+            if self.started == tests.len() {
+                return;
+            }
+
+            let test_actor = test_actors[self.started];
+            self.send(TestEvent::Started {
+                name: test_actor.name().to_string(),
+                tests: test_actor.abi().methods().len(),
+            });
+            if let Err(err) = &tests[self.started] {
+                let msg = match err.source() {
+                    Some(source) => format!("{err}\nCaused by: {source}"),
+                    None => err.to_string(),
+                };
+                self.send(TestEvent::SetupFailed(msg));
+            }
+            self.started += 1;
+        }
+    }
+
+    fn send(&self, event: TestEvent) {
+        if let Some(sender) = self.sender {
+            if let Err(err) = sender.send(event) {
+                log::error!("Could not Stream the Result: {err}");
+            }
+        }
+    }
+}
+
+/// Context shared by the threads running the test methods.
+pub(crate) struct Runner<'a> {
+    pub engine: &'a EnginePool,
+    pub builtin_actors: Cid,
+    pub account_address: Address,
+    pub target: &'a DeployedActor,
+    pub target_actor_id: ActorID,
+    // Blockstore containing the state of every prepared test.
+    pub blockstore: Mutex<MemoryBlockstore>,
+    pub fuzz_config: FuzzConfig,
+    pub invariant_config: InvariantConfig,
+    pub externs: &'a FakeExterns,
+    pub keyring: &'a Keyring,
+    pub registry: &'a ActorRegistry,
+    pub fixtures: &'a BTreeMap<String, ActorID>,
+    pub gas_config: &'a GasConfig,
+}
+
+impl Runner<'_> {
+    /// Run the methods of the prepared tests on `jobs` threads, returning the results of each
+    /// test Actor, none for those that could not be prepared. Progress is streamed in the order of
+    /// the test Actors and of their methods.
+    pub fn run(
+        &self,
+        test_actors: &[&WasmActor],
+        tests: &[Result<PreparedTest, Error>],
+        jobs: usize,
+        stream_results: Option<&Sender<TestEvent>>,
+    ) -> Vec<Vec<TestResult>> {
+        // Index of the first result of each test, followed by the number of results.
+        let mut offsets = vec![0];
+        let mut queue = vec![];
+        for (test, prepared) in tests.iter().enumerate() {
+            let methods = match prepared {
+                Ok(prepared) => prepared.test_actor.abi().methods(),
+                Err(_) => &[],
+            };
+            offsets.push(offsets[test] + methods.len());
+
+            let invariants = methods
+                .iter()
+                .enumerate()
+                .filter(|(_, method)| method.r#type() == MethodType::Invariant)
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>();
+            if !invariants.is_empty() {
+                queue.push(Job::Invariants {
+                    test,
+                    methods: invariants,
+                });
+            }
+            queue.extend(
+                methods
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, method)| method.r#type() != MethodType::Invariant)
+                    .map(|(method, _)| Job::Method { test, method }),
+            );
+        }
+
+        let mut results: Vec<Option<TestResult>> = vec![None; offsets[tests.len()]];
+        let mut stream = Stream {
+            sender: stream_results,
+            started: 0,
+            streamed: 0,
+        };
+        let next_job = AtomicUsize::new(0);
+        let (results_tx, results_rx) = channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, queue.len().max(1)) {
+                let results_tx = results_tx.clone();
+                let (next_job, queue, offsets) = (&next_job, &queue, &offsets);
+                scope.spawn(move || {
+                    while let Some(job) = queue.get(next_job.fetch_add(1, Ordering::Relaxed)) {
+                        if results_tx.send(self.run_job(tests, offsets, job)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(results_tx);
+
+            // Stream the results as soon as all the previous ones are available.
+            stream.advance(test_actors, tests, &offsets, &results);
+            for outcomes in results_rx {
+                for (index, result) in outcomes {
+                    results[index] = Some(result);
+                }
+                stream.advance(test_actors, tests, &offsets, &results);
+            }
+        });
+
+        let mut results = results
+            .into_iter()
+            .map(|result| result.expect("Every test method should have a result"));
+        offsets
+            .windows(2)
+            .map(|window| results.by_ref().take(window[1] - window[0]).collect())
+            .collect()
+    }
+
+    /// Run a [`Job`], returning the results with their index.
+    fn run_job(
+        &self,
+        tests: &[Result<PreparedTest, Error>],
+        offsets: &[usize],
+        job: &Job,
+    ) -> Vec<(usize, TestResult)> {
+        match job {
+            Job::Method { test, method } => {
+                let prepared = prepared(tests, *test);
+                let result =
+                    self.test_method(prepared, &prepared.test_actor.abi().methods()[*method]);
+                vec![(offsets[*test] + method, result)]
+            }
+            Job::Invariants { test, methods } => {
+                let prepared = prepared(tests, *test);
+                let invariants = methods
+                    .iter()
+                    .map(|method| &prepared.test_actor.abi().methods()[*method])
+                    .collect::<Vec<&Method>>();
+                let campaign = InvariantCampaign {
+                    engine: self.engine,
+                    externs: self.externs,
+                    keyring: self.keyring,
+                    registry: self.registry,
+                    fixtures: self.fixtures,
+                    gas_config: self.gas_config,
+                    builtin_actors: self.builtin_actors,
+                    account_address: self.account_address,
+                    target_address: *self.target.address(),
+                    target_id: self.target_id(),
+                    test_address: prepared.test_address,
+                    entrypoints: self
+                        .target
+                        .abi()
+                        .methods()
+                        .iter()
+                        .filter(|method| method.r#type() == MethodType::Entrypoint)
+                        .collect(),
+                    invariants: invariants.clone(),
+                    root: prepared.root,
+                    blockstore: self.blockstore(),
+                    circ_supply_delta: prepared.circ_supply_delta.clone(),
+                    sequence: prepared.sequence,
+                    config: self.invariant_config,
+                    seed: self.fuzz_config.seed,
+                };
+
+                methods
+                    .iter()
+                    .zip(invariants)
+                    .zip(campaign.run())
+                    .map(|((index, method), (ret, report))| {
+                        let result = TestResult {
+                            invariant: Some(report),
+                            labels: self.keyring.labels().clone(),
+                            ..TestResult::new(method.clone(), ret)
+                        };
+                        (offsets[*test] + index, result)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Serialized target Actor ID passed to the test methods.
+    fn target_id(&self) -> RawBytes {
+        RawBytes::new(
+            fvm_ipld_encoding::to_vec(&self.target_actor_id)
+                .expect("Should be able to serialize target actor ID"),
+        )
+    }
+
+    /// Get a copy of the shared blockstore.
+    fn blockstore(&self) -> MemoryBlockstore {
+        self.blockstore
+            .lock()
+            .expect("Should be able to lock the blockstore")
+            .clone()
+    }
+
+    /// Instantiate a new [`KytheraExecutor`] over the state of a prepared test.
+    fn executor(&self, prepared: &PreparedTest) -> KytheraExecutor {
+        // TODO is it possible to impl `Clone` for `DefaultExecutor`
+        // and submit PR upstream to implement with it?
+        KytheraExecutor::new(
+            self.engine.clone(),
+            self.blockstore(),
+            prepared.root,
+            self.builtin_actors,
+            self.account_address,
+            self.target_id(),
+            self.externs.clone(),
+        )
+        .with_keyring(self.keyring.clone())
+        .with_registry(self.registry.clone())
+        .with_fixtures(self.fixtures.clone())
+        .with_circ_supply_delta(prepared.circ_supply_delta.clone())
+        .with_gas_config(self.gas_config.clone())
+    }
+
+    /// Run a test method that is not an `Invariant`.
+    fn test_method(&self, prepared: &PreparedTest, method: &Method) -> TestResult {
+        log::debug!(
+            "Testing test {}.{}() for Actor {}",
+            prepared.test_actor.name(),
+            method.name(),
+            self.target.name()
+        );
+
+        if method.r#type() == MethodType::TestFuzz {
+            let (ret, report) = self.fuzz_method(prepared, method);
+            return TestResult {
+                fuzz: Some(report),
+                labels: self.keyring.labels().clone(),
+                ..TestResult::new(method.clone(), ret)
+            };
+        }
+
+        let mut executor = self.executor(prepared);
+        let message = executor.execute_method_with_value(
+            prepared.test_address,
+            method.number(),
+            self.target_id(),
+            TokenAmount::from_atto(method.value()),
+            prepared.sequence,
+        );
+
+        let ret = match message {
+            Ok(apply_ret) => TestResultType::applied(
+                apply_ret,
+                method.r#type() == MethodType::TestFail,
+                executor.gas_limited(),
+            ),
+            Err(err) => TestResultType::erred(err),
+        };
+        // Accounts created by the cheatcodes during the test are labelled too.
+        let labels = executor.keyring().labels().clone();
+
+        // Keep the state after the test so that it can be inspected.
+        let (root, blockstore) = executor.into_store();
+        let state_root = match copy_reachable(&blockstore, &self.blockstore, root) {
+            Ok(()) => Some(root),
+            Err(err) => {
+                log::warn!(
+                    "Could not keep the state after test {}: {err}",
+                    method.name()
+                );
+                None
+            }
+        };
+
+        TestResult {
+            labels,
+            state_root,
+            ..TestResult::new(method.clone(), ret)
+        }
+    }
+
+    /// Run a `TestFuzz` method with the given inputs.
+    fn fuzz_run(
+        &self,
+        prepared: &PreparedTest,
+        method: &Method,
+        inputs: &[FuzzValue],
+    ) -> TestResultType {
+        let mut executor = self.executor(prepared);
+        let message = executor.execute_method_with_value(
+            prepared.test_address,
+            method.number(),
+            fuzz::encode_params(self.target_actor_id, inputs),
+            TokenAmount::from_atto(method.value()),
+            prepared.sequence,
+        );
+
+        match message {
+            Ok(apply_ret) => TestResultType::applied(apply_ret, false, executor.gas_limited()),
+            Err(err) => TestResultType::erred(err),
+        }
+    }
+
+    /// Run a `TestFuzz` method with generated inputs, shrinking the first failing ones.
+    fn fuzz_method(
+        &self,
+        prepared: &PreparedTest,
+        method: &Method,
+    ) -> (TestResultType, FuzzReport) {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.fuzz_config.seed ^ method.number());
+        let runs = self.fuzz_config.runs.max(1);
+        let mut last = None;
+
+        for run in 1..=runs {
+            let inputs = fuzz::generate_inputs(method.params(), &mut rng);
+            match self.fuzz_run(prepared, method, &inputs) {
+                TestResultType::Passed(apply_ret) => last = Some(apply_ret),
+                ret if ret.is_failure() => {
+                    let (inputs, ret) = self.fuzz_shrink(prepared, method, inputs, ret);
+                    return (ret, FuzzReport::new(run, Some(inputs)));
+                }
+                ret => return (ret, FuzzReport::new(run, Some(inputs))),
+            }
+        }
+
+        (
+            TestResultType::Passed(last.expect("At least one fuzz run should have been executed")),
+            FuzzReport::new(runs, None),
+        )
+    }
+
+    /// Shrink failing inputs of a `TestFuzz` method to simpler ones that still fail.
+    fn fuzz_shrink(
+        &self,
+        prepared: &PreparedTest,
+        method: &Method,
+        inputs: Vec<FuzzValue>,
+        ret: TestResultType,
+    ) -> (Vec<FuzzValue>, TestResultType) {
+        let (inputs, ret, shrink_runs) = fuzz::shrink(inputs, ret, |candidate| {
+            Some(self.fuzz_run(prepared, method, candidate)).filter(TestResultType::is_failure)
+        });
+        log::debug!(
+            "Shrunk counterexample of {}() in {shrink_runs} runs",
+            method.name()
+        );
+
+        (inputs, ret)
+    }
+}
+
+/// Get a test that has been prepared, the [`Job`]s only running those.
+fn prepared<'t, 'a>(
+    tests: &'t [Result<PreparedTest<'a>, Error>],
+    test: usize,
+) -> &'t PreparedTest<'a> {
+    tests[test]
+        .as_ref()
+        .expect("Jobs should only run prepared tests")
+}
+
+/// Copy the blocks reachable from `root` that are missing from the shared blockstore.
+fn copy_reachable(
+    from: &MemoryBlockstore,
+    to: &Mutex<MemoryBlockstore>,
+    root: Cid,
+) -> anyhow::Result<()> {
+    let to = to.lock().expect("Should be able to lock the blockstore");
+    let mut stack = vec![root];
+    while let Some(cid) = stack.pop() {
+        if to.has(&cid)? {
+            continue;
+        }
+        // Identity CIDs inline their data and are not stored.
+        let data = match from.get(&cid)? {
+            Some(data) => data,
+            None => continue,
+        };
+        if cid.codec() == DAG_CBOR {
+            let ipld: Ipld = fvm_ipld_encoding::from_slice(&data)?;
+            ipld.references(&mut stack);
+        }
+        to.put_keyed(&cid, &data)?;
+    }
+    Ok(())
+}
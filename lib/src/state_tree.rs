@@ -8,15 +8,21 @@ use crate::{
 
 use cid::{multihash::Code, Cid};
 use futures::executor::block_on;
+use fvm_ipld_amt::Amt;
 use fvm_ipld_blockstore::{Block, Blockstore, MemoryBlockstore};
 use fvm_ipld_car::load_car_unchecked;
-use fvm_ipld_encoding::{serde::Serialize, CborStore};
+use fvm_ipld_encoding::{de::DeserializeOwned, serde::Serialize, CborStore};
+use fvm_ipld_hamt::{BytesKey, Hamt};
 use fvm_shared::{
     address::Address, econ::TokenAmount, state::StateTreeVersion, ActorID, HAMT_BIT_WIDTH, IPLD_RAW,
 };
-use kythera_fvm::{account_actor, machine::Manifest, state_tree::ActorState, Account};
-use libsecp256k1::{PublicKey, SecretKey};
-use rand::SeedableRng;
+use kythera_fvm::{
+    keys::{account_actor_state, TestKey},
+    machine::Manifest,
+    state_tree::ActorState,
+    Account,
+};
+use std::collections::BTreeMap;
 
 use fil_actors_runtime_v10::runtime::builtins::Type;
 use fil_actors_runtime_v10::{
@@ -65,6 +71,126 @@ impl StateTree {
         }
     }
 
+    /// Retrieve the ID of an actor from its address, if it exists in the [`StateTree`].
+    pub fn actor_id(&self, address: &Address) -> Result<Option<ActorID>, Error> {
+        self.inner
+            .lookup_id(address)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not look up actor {address}: {err}"),
+            })
+    }
+
+    /// Retrieve the balance of an actor at a given state root of the [`StateTree`] store, zero if
+    /// the actor does not exist at that root.
+    pub fn actor_balance_at(&self, root: &Cid, actor_id: ActorID) -> Result<TokenAmount, Error> {
+        let state_tree = kythera_fvm::state_tree::StateTree::new_from_root(self.store(), root)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not load state root {root}: {err}"),
+            })?;
+        match state_tree.get_actor(actor_id) {
+            Ok(actor) => Ok(actor.map(|actor| actor.balance).unwrap_or_default()),
+            Err(err) => Err(Error::StateTree {
+                msg: format!("Could not get actor {actor_id}: {err}"),
+            }),
+        }
+    }
+
+    /// Retrieve the state of an actor at a given state root of the [`StateTree`] store, if it
+    /// exists at that root.
+    pub fn actor_state_at(
+        &self,
+        root: &Cid,
+        address: &Address,
+    ) -> Result<Option<ActorState>, Error> {
+        let state_tree = kythera_fvm::state_tree::StateTree::new_from_root(self.store(), root)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not load state root {root}: {err}"),
+            })?;
+        let actor_id = match state_tree.lookup_id(address) {
+            Ok(Some(actor_id)) => actor_id,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                return Err(Error::StateTree {
+                    msg: format!("Could not look up actor {address}: {err}"),
+                })
+            }
+        };
+        state_tree
+            .get_actor(actor_id)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not get actor {actor_id}: {err}"),
+            })
+    }
+
+    /// Retrieve the state of an actor, if it exists in the [`StateTree`].
+    pub fn actor_state(&self, actor_id: ActorID) -> Result<Option<ActorState>, Error> {
+        self.inner
+            .get_actor(actor_id)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not get actor {actor_id}: {err}"),
+            })
+    }
+
+    /// Remove an actor from the [`StateTree`].
+    pub fn delete_actor(&mut self, actor_id: ActorID) {
+        self.inner.delete_actor(actor_id)
+    }
+
+    /// Decode a CBOR block of the [`StateTree`] store.
+    pub fn decode_block<T: DeserializeOwned>(&self, cid: &Cid) -> Result<T, Error> {
+        match self.store().get_cbor(cid) {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(Error::StateTree {
+                msg: format!("Missing block in state tree store: {cid}"),
+            }),
+            Err(err) => Err(Error::StateTree {
+                msg: format!("Could not decode block {cid}: {err}"),
+            }),
+        }
+    }
+
+    /// Retrieve the entries of a HAMT of the [`StateTree`] store by key bytes.
+    pub fn hamt_entries<V: Serialize + DeserializeOwned + Clone>(
+        &self,
+        root: &Cid,
+        bit_width: u32,
+    ) -> Result<BTreeMap<Vec<u8>, V>, Error> {
+        let hamt = Hamt::<_, V, BytesKey>::load_with_bit_width(root, self.store(), bit_width)
+            .map_err(|err| Error::StateTree {
+                msg: format!("Could not load HAMT {root}: {err}"),
+            })?;
+
+        let mut entries = BTreeMap::new();
+        hamt.for_each(|key, value| {
+            entries.insert(key.0.clone(), value.clone());
+            Ok(())
+        })
+        .map_err(|err| Error::StateTree {
+            msg: format!("Could not traverse HAMT {root}: {err}"),
+        })?;
+        Ok(entries)
+    }
+
+    /// Retrieve the entries of an AMT of the [`StateTree`] store by index.
+    pub fn amt_entries<V: Serialize + DeserializeOwned + Clone>(
+        &self,
+        root: &Cid,
+    ) -> Result<BTreeMap<u64, V>, Error> {
+        let amt = Amt::<V, _>::load(root, self.store()).map_err(|err| Error::StateTree {
+            msg: format!("Could not load AMT {root}: {err}"),
+        })?;
+
+        let mut entries = BTreeMap::new();
+        amt.for_each(|index, value| {
+            entries.insert(index, value.clone());
+            Ok(())
+        })
+        .map_err(|err| Error::StateTree {
+            msg: format!("Could not traverse AMT {root}: {err}"),
+        })?;
+        Ok(entries)
+    }
+
     pub fn flush(&mut self) -> cid::CidGeneric<64> {
         self.inner
             .flush()
@@ -355,42 +481,46 @@ impl StateTree {
         .expect("Should be able to load cheatcodes actor");
     }
 
-    /// Creates new accounts in the testing context
-    /// Inserts the account in the state tree, all with the provided balance, returning it and its public key address.
-    pub fn create_account(&mut self, accounts_code_cid: Cid) -> Account {
-        let rng = &mut rand_chacha::ChaCha8Rng::seed_from_u64(8);
-
-        let priv_key = SecretKey::random(rng);
+    /// Create the account of a key in the testing context, provided with a given token balance,
+    /// returning its ID and key address.
+    pub fn create_account(
+        &mut self,
+        manifest: &Manifest,
+        key: &TestKey,
+        balance: TokenAmount,
+    ) -> Result<Account, Error> {
+        let key_addr = key.address();
 
-        let pub_key = PublicKey::from_secret_key(&priv_key);
-        let pub_key_addr =
-            Address::new_secp256k1(&pub_key.serialize()).expect("PublicKey length should be valid");
+        if self.inner.lookup_id(&key_addr).ok().flatten().is_some() {
+            return Err(Error::StateTree {
+                msg: format!("Account already exists in state tree: {key_addr}"),
+            });
+        }
 
         let assigned_addr = self
             .inner
-            .register_new_address(&pub_key_addr)
-            .expect("Should be able to register an account public key on the StateTree");
-
-        let state = account_actor::State {
-            address: pub_key_addr,
-        };
+            .register_new_address(&key_addr)
+            .expect("Should be able to register an account key address on the StateTree");
 
-        let cid = self
-            .inner
-            .store()
-            .put_cbor(&state, Code::Blake2b256)
+        let actor_state = account_actor_state(key, manifest, self.inner.store(), balance)
             .expect("Should be able to put the Actor State as CBOR");
 
-        let actor_state = ActorState {
-            code: accounts_code_cid,
-            state: cid,
-            sequence: 0,
-            balance: TokenAmount::from_atto(10000),
-            delegated_address: None,
-        };
-
         self.inner.set_actor(assigned_addr, actor_state);
-        (assigned_addr, pub_key_addr)
+        Ok((assigned_addr, key_addr))
+    }
+
+    /// Put the code of an Actor into the `Blockstore` and returns its CodeCID.
+    pub fn put_actor_code(&self, actor: &WasmActor) -> Result<Cid, Error> {
+        self.inner
+            .store()
+            .put(
+                Code::Blake2b256,
+                &Block {
+                    codec: IPLD_RAW,
+                    data: &actor.bytecode,
+                },
+            )
+            .setting_err(&actor.name)
     }
 
     /// Deploy a new Actor at a given address, provided with a given token balance and returns the
@@ -403,17 +533,7 @@ impl StateTree {
     ) -> Result<(), Error> {
         // Put the WASM code into the blockstore.
         log::debug!("Deploying Actor {} code", actor.name);
-        let code_cid = self
-            .inner
-            .store()
-            .put(
-                Code::Blake2b256,
-                &Block {
-                    codec: IPLD_RAW,
-                    data: &actor.bytecode,
-                },
-            )
-            .setting_err(&actor.name)?;
+        let code_cid = self.put_actor_code(actor)?;
 
         // Set the Actor State on the `BlockStore`.
         self.set_actor(
@@ -445,6 +565,53 @@ impl StateTree {
         Ok(actor_address_id)
     }
 
+    /// Deploy a new Actor at a given ID, provided with a given token balance. The ID should not be
+    /// used, and the IDs assigned to the next Actors start after it.
+    pub fn deploy_actor_from_bin_at_id(
+        &mut self,
+        id: ActorID,
+        actor: &WasmActor,
+        balance: TokenAmount,
+    ) -> Result<Address, Error> {
+        if self.inner.get_actor(id).ok().flatten().is_some() {
+            return Err(Error::StateTree {
+                msg: format!("Actor already exists in state tree: {id}"),
+            });
+        }
+
+        // Move the next ID assigned by the init actor past the fixed one.
+        let mut init_actor =
+            self.inner
+                .get_actor(INIT_ACTOR_ID)
+                .ok()
+                .flatten()
+                .ok_or(Error::MissingActor {
+                    msg: format!("Missing actor in state tree: {INIT_ACTOR_ID}"),
+                })?;
+        let mut init_state: fil_actor_init_v10::State = self
+            .inner
+            .store()
+            .get_cbor(&init_actor.state)
+            .ok()
+            .flatten()
+            .ok_or(Error::StateTree {
+                msg: String::from("Could not load the Init Actor state"),
+            })?;
+        if init_state.next_id <= id {
+            init_state.next_id = id + 1;
+            init_actor.state = self
+                .inner
+                .store()
+                .put_cbor(&init_state, Code::Blake2b256)
+                .setting_err("Init Actor")?;
+            self.inner.set_actor(INIT_ACTOR_ID, init_actor);
+        }
+
+        let address = Address::new_id(id);
+        self.deploy_actor_from_bin_at_address(&address, actor, balance)?;
+        Ok(address)
+    }
+
     /// Override current inner `StateTree` with a new `Blockstore` and root `Cid`
     pub fn override_inner(&mut self, blockstore: MemoryBlockstore, root: Cid) -> Result<(), Error> {
         if !blockstore
@@ -1,13 +1,21 @@
 use fvm_ipld_encoding::from_slice;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use kythera_actors::wasm_bin::test_actors::{
     BASIC_TARGET_ACTOR_BINARY, BASIC_TEST_ACTOR_BINARY, BUILTINS_TEST_ACTOR_BINARY,
-    CHEATCODES_TEST_ACTOR_BINARY, FAIL_TEST_ACTOR_BINARY,
+    CHEATCODES_TEST_ACTOR_BINARY, FAIL_TEST_ACTOR_BINARY, FUZZ_TEST_ACTOR_BINARY,
+    INVARIANT_TARGET_ACTOR_BINARY, INVARIANT_TEST_ACTOR_BINARY,
 };
-use kythera_common::abi::{Abi, Method, MethodType};
+use kythera_common::abi::{Abi, Method, MethodType, ParamType};
 use kythera_fvm::executor::ApplyFailure::MessageBacktrace;
 use kythera_lib::error::Error;
-use kythera_lib::{TestResultType, Tester, WasmActor};
+use kythera_lib::{
+    FixtureActor, FuzzConfig, FuzzValue, InvariantConfig, RawBytes, TestEvent, TestResultType,
+    Tester, WasmActor,
+};
+use std::sync::mpsc::channel;
 
 fn set_target_actor(tester: &mut Tester, name: String, binary: Vec<u8>, abi: Abi) {
     let target_actor = WasmActor::new(name, binary, abi);
@@ -100,6 +108,44 @@ fn test_failing_target_actor_constructor() {
     }
 }
 
+#[test]
+fn test_target_actor_constructor_params() {
+    // Instantiate tester
+    let mut tester = Tester::new();
+
+    // Set target actor with the parameters and value of its constructor
+    let target_actor = WasmActor::new(
+        String::from("Target.wasm"),
+        Vec::from(BASIC_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: Some(Method::new_from_name("Constructor").unwrap()),
+            set_up: None,
+            methods: vec![Method::new_from_name("HelloWorld").unwrap()],
+        },
+    )
+    .with_constructor_params(
+        RawBytes::serialize("Configured Target Actor").unwrap(),
+        TokenAmount::from_atto(1000),
+    );
+    tester
+        .deploy_target_actor(target_actor)
+        .expect("Could not set target Actor when testing constructor parameters");
+    let target = *tester
+        .deployed_actor()
+        .expect("Target Actor should be deployed")
+        .address();
+
+    // The constructor received the parameters and the value
+    let (who_am_i,): (String,) = tester
+        .decode_state(&target)
+        .expect("Should be able to decode the target state");
+    assert_eq!(who_am_i, "Configured Target Actor");
+    let state = tester
+        .actor_state(&target)
+        .expect("Should be able to get the target actor");
+    assert_eq!(state.balance, TokenAmount::from_atto(1000));
+}
+
 #[test]
 fn test_failing_test_actor_constructor_setup() {
     // Instantiate tester
@@ -138,7 +184,7 @@ fn test_failing_test_actor_constructor_setup() {
 
     // Run test
     for test_actor in &[constructor_test_actor.clone(), setup_test_actor.clone()] {
-        match tester.test(&test_actor, None) {
+        match tester.test(test_actor, None) {
             Err(err) => {
                 if test_actor.name().contains("Constructor") {
                     if !matches!(err, Error::Constructor { .. }) {
@@ -235,6 +281,9 @@ fn test_tester_flow() {
             Method::new_from_name("TestFailed").unwrap(),
             Method::new_from_name("TestFailFailed").unwrap(),
             Method::new_from_name("TestFailSuccess").unwrap(),
+            Method::new_from_name("TestValueReceived")
+                .unwrap()
+                .with_value(1000),
         ],
     };
 
@@ -245,7 +294,7 @@ fn test_tester_flow() {
             panic!("Could not run test when testing Tester flow")
         }
         Ok(test_res) => {
-            assert_eq!(test_res.len(), 5);
+            assert_eq!(test_res.len(), 6);
             test_res
                 .iter()
                 .for_each(|result| match result.method().name() {
@@ -290,12 +339,130 @@ fn test_tester_flow() {
                         }
                         _ => panic!("TestFailSuccess should be passing with non ExitCode::OK"),
                     },
+                    "TestValueReceived" => match &result.ret() {
+                        TestResultType::Passed(apply_ret) => {
+                            assert_eq!(apply_ret.msg_receipt.exit_code, ExitCode::OK)
+                        }
+                        _ => panic!("TestValueReceived should be passing with ExitCode::OK"),
+                    },
                     name => panic!("Test case not handled for: {}", name),
                 })
         }
     }
 }
 
+#[test]
+fn test_parallel_jobs() {
+    // Instantiate tester running the tests on several threads
+    let mut tester = Tester::new();
+    tester.set_jobs(4);
+
+    // Set target actor
+    set_target_actor(
+        &mut tester,
+        String::from("Target.wasm"),
+        Vec::from(BASIC_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: Some(Method::new_from_name("Constructor").unwrap()),
+            set_up: None,
+            methods: vec![
+                Method::new_from_name("HelloWorld").unwrap(),
+                Method::new_from_name("Caller").unwrap(),
+                Method::new_from_name("Origin").unwrap(),
+            ],
+        },
+    );
+
+    // Set test actors, one of them not being valid
+    let test_abi = Abi {
+        constructor: Some(Method::new_from_name("Constructor").unwrap()),
+        set_up: Some(Method::new_from_name("Setup").unwrap()),
+        methods: vec![
+            Method::new_from_name("TestConstructorSetup").unwrap(),
+            Method::new_from_name("TestMethodParameter").unwrap(),
+            Method::new_from_name("TestFailed").unwrap(),
+            Method::new_from_name("TestFailFailed").unwrap(),
+            Method::new_from_name("TestFailSuccess").unwrap(),
+        ],
+    };
+    let first_actor = WasmActor::new(
+        String::from("First.t.wasm"),
+        Vec::from(BASIC_TEST_ACTOR_BINARY),
+        test_abi.clone(),
+    );
+    let non_valid_actor = WasmActor::new(
+        String::from("NonValid.t.wasm"),
+        vec![1, 2, 3],
+        test_abi.clone(),
+    );
+    let second_actor = WasmActor::new(
+        String::from("Second.t.wasm"),
+        Vec::from(BASIC_TEST_ACTOR_BINARY),
+        test_abi.clone(),
+    );
+
+    let (stream_tx, stream_rx) = channel();
+    let results = tester
+        .test_all(
+            &[&first_actor, &non_valid_actor, &second_actor],
+            Some(stream_tx),
+        )
+        .expect("Could not run tests when testing Tester with several jobs");
+
+    // Results should be returned in the order of the test actors and of their methods.
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].test_actor, &first_actor);
+    assert_eq!(results[1].test_actor, &non_valid_actor);
+    assert!(matches!(results[1].results, Err(Error::Tester { .. })));
+    assert_eq!(results[2].test_actor, &second_actor);
+    for test_actor_results in [&results[0], &results[2]] {
+        let test_res = test_actor_results.results.as_ref().unwrap();
+        let names = test_res
+            .iter()
+            .map(|result| result.method().name())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            names,
+            test_abi
+                .methods
+                .iter()
+                .map(|method| method.name())
+                .collect::<Vec<&str>>()
+        );
+        let passed = test_res
+            .iter()
+            .map(|result| result.passed())
+            .collect::<Vec<bool>>();
+        assert_eq!(passed, vec![true, true, false, false, true]);
+    }
+
+    // Results should be streamed in the same order, each test actor starting before its results
+    // and its setup error.
+    let streamed = stream_rx
+        .iter()
+        .map(|event| match event {
+            TestEvent::Started { name, tests } => format!("start {name} {tests}"),
+            TestEvent::SetupFailed(_) => String::from("setup failed"),
+            TestEvent::Result(result) => result.method().name().to_string(),
+        })
+        .collect::<Vec<String>>();
+    let mut expected = vec![];
+    for actor in [&first_actor, &non_valid_actor, &second_actor] {
+        expected.push(format!("start {} 5", actor.name()));
+        if actor == &non_valid_actor {
+            expected.push(String::from("setup failed"));
+            continue;
+        }
+        expected.extend(
+            test_abi
+                .methods
+                .iter()
+                .map(|method| method.name().to_string()),
+        );
+    }
+    assert_eq!(streamed, expected);
+}
+
 macro_rules! generate_match_assert {
         ($apply_failure:expr, $result:expr, $($test_name:expr => $test_message:expr),*) => {{
         match $apply_failure {
@@ -334,6 +501,47 @@ fn test_cheatcodes() {
         },
     );
 
+    // Register the target actor with its constructor so that it can be deployed by the tests
+    tester
+        .register_actor(&WasmActor::new(
+            String::from("Target.wasm"),
+            Vec::from(BASIC_TARGET_ACTOR_BINARY),
+            Abi {
+                constructor: Some(Method::new_from_name("Constructor").unwrap()),
+                set_up: None,
+                methods: vec![Method::new_from_name("HelloWorld").unwrap()],
+            },
+        ))
+        .expect("Could not register target Actor");
+
+    // Register an actor whose constructor fails, to check failing deployments
+    tester
+        .register_actor(&WasmActor::new(
+            String::from("Failing.wasm"),
+            Vec::from(FAIL_TEST_ACTOR_BINARY),
+            Abi {
+                constructor: Some(Method::new_from_name("Constructor").unwrap()),
+                set_up: None,
+                methods: vec![],
+            },
+        ))
+        .expect("Could not register failing Actor");
+
+    // Deploy a fixture actor along with the target actor
+    tester
+        .deploy_fixture(&[FixtureActor {
+            name: String::from("Token"),
+            actor: WasmActor::new(
+                String::from("Token.wasm"),
+                Vec::from(BASIC_TARGET_ACTOR_BINARY),
+                Abi::default(),
+            ),
+            constructor_params: RawBytes::default(),
+            balance: TokenAmount::zero(),
+            id: None,
+        }])
+        .expect("Could not deploy fixture Actor");
+
     // Set test actor
     let test_wasm_bin: Vec<u8> = Vec::from(CHEATCODES_TEST_ACTOR_BINARY);
     let test_abi = Abi {
@@ -356,6 +564,11 @@ fn test_cheatcodes() {
             Method::new_from_name("TestFailDeserializationPrank").unwrap(),
             Method::new_from_name("TestFailNoParametersPrank").unwrap(),
             Method::new_from_name("TestFailAddressTypePrank").unwrap(),
+            Method::new_from_name("TestPrankOrigin").unwrap(),
+            Method::new_from_name("TestStartPrank").unwrap(),
+            Method::new_from_name("TestFailDeserializationStartPrank").unwrap(),
+            Method::new_from_name("TestFailNoParametersStartPrank").unwrap(),
+            Method::new_from_name("TestFailAddressTypeStartPrank").unwrap(),
             Method::new_from_name("TestTrick").unwrap(),
             Method::new_from_name("TestFailDeserializationTrick").unwrap(),
             Method::new_from_name("TestFailNoParametersTrick").unwrap(),
@@ -365,6 +578,109 @@ fn test_cheatcodes() {
             Method::new_from_name("TestFailNoParametersAlter").unwrap(),
             Method::new_from_name("TestFailInvalidAddressAlter").unwrap(),
             Method::new_from_name("TestFailInvalidCidAlter").unwrap(),
+            Method::new_from_name("TestExpectRevert").unwrap(),
+            Method::new_from_name("TestFailDeserializationExpectRevert").unwrap(),
+            Method::new_from_name("TestFailNoParametersExpectRevert").unwrap(),
+            Method::new_from_name("TestFailSuccessExitCodeExpectRevert").unwrap(),
+            Method::new_from_name("TestFailCallSucceededExpectRevert").unwrap(),
+            Method::new_from_name("TestFailExitCodeExpectRevert").unwrap(),
+            Method::new_from_name("TestFailMessageExpectRevert").unwrap(),
+            Method::new_from_name("TestDeal").unwrap(),
+            Method::new_from_name("TestDealReverted").unwrap(),
+            Method::new_from_name("TestFailDeserializationDeal").unwrap(),
+            Method::new_from_name("TestFailNoParametersDeal").unwrap(),
+            Method::new_from_name("TestFailNegativeBalanceDeal").unwrap(),
+            Method::new_from_name("TestFailInvalidAddressDeal").unwrap(),
+            Method::new_from_name("TestExpectEmit").unwrap(),
+            Method::new_from_name("TestFailDeserializationExpectEmit").unwrap(),
+            Method::new_from_name("TestFailNoParametersExpectEmit").unwrap(),
+            Method::new_from_name("TestFailEmptyEventExpectEmit").unwrap(),
+            Method::new_from_name("TestFailEventExpectEmit").unwrap(),
+            Method::new_from_name("TestFailNoEventExpectEmit").unwrap(),
+            Method::new_from_name("TestSnapshot").unwrap(),
+            Method::new_from_name("TestFailDeserializationRevertTo").unwrap(),
+            Method::new_from_name("TestFailNoParametersRevertTo").unwrap(),
+            Method::new_from_name("TestFailInvalidIdRevertTo").unwrap(),
+            Method::new_from_name("TestMockCall").unwrap(),
+            Method::new_from_name("TestFailDeserializationMockCall").unwrap(),
+            Method::new_from_name("TestFailNoParametersMockCall").unwrap(),
+            Method::new_from_name("TestExpectCall").unwrap(),
+            Method::new_from_name("TestFailDeserializationExpectCall").unwrap(),
+            Method::new_from_name("TestFailNoParametersExpectCall").unwrap(),
+            Method::new_from_name("TestFailCountExpectCall").unwrap(),
+            Method::new_from_name("TestFailValueExpectCall").unwrap(),
+            Method::new_from_name("TestFailAbortExpectCall").unwrap(),
+            Method::new_from_name("TestSetChainRandomness").unwrap(),
+            Method::new_from_name("TestFailDeserializationSetChainRandomness").unwrap(),
+            Method::new_from_name("TestFailNoParametersSetChainRandomness").unwrap(),
+            Method::new_from_name("TestFailLengthSetChainRandomness").unwrap(),
+            Method::new_from_name("TestSetBeaconRandomness").unwrap(),
+            Method::new_from_name("TestFailDeserializationSetBeaconRandomness").unwrap(),
+            Method::new_from_name("TestFailNoParametersSetBeaconRandomness").unwrap(),
+            Method::new_from_name("TestFailLengthSetBeaconRandomness").unwrap(),
+            Method::new_from_name("TestSetTipsetCid").unwrap(),
+            Method::new_from_name("TestFailDeserializationSetTipsetCid").unwrap(),
+            Method::new_from_name("TestFailNoParametersSetTipsetCid").unwrap(),
+            Method::new_from_name("TestFailInvalidCidSetTipsetCid").unwrap(),
+            Method::new_from_name("TestMockConsensusFault").unwrap(),
+            Method::new_from_name("TestFailDeserializationMockConsensusFault").unwrap(),
+            Method::new_from_name("TestFailNoParametersMockConsensusFault").unwrap(),
+            Method::new_from_name("TestFailFaultTypeMockConsensusFault").unwrap(),
+            Method::new_from_name("TestFailAddressTypeMockConsensusFault").unwrap(),
+            Method::new_from_name("TestMockProofs").unwrap(),
+            Method::new_from_name("TestFailDeserializationMockProofs").unwrap(),
+            Method::new_from_name("TestFailNoParametersMockProofs").unwrap(),
+            Method::new_from_name("TestExpectVerifyProof").unwrap(),
+            Method::new_from_name("TestFailDeserializationExpectVerifyProof").unwrap(),
+            Method::new_from_name("TestFailNoParametersExpectVerifyProof").unwrap(),
+            Method::new_from_name("TestFailKindExpectVerifyProof").unwrap(),
+            Method::new_from_name("TestFailUnverifiedExpectVerifyProof").unwrap(),
+            Method::new_from_name("TestNewAccount").unwrap(),
+            Method::new_from_name("TestKeyTypesNewAccount").unwrap(),
+            Method::new_from_name("TestFailKeyTypeNewAccount").unwrap(),
+            Method::new_from_name("TestFailDeserializationNewAccount").unwrap(),
+            Method::new_from_name("TestFailNoParametersNewAccount").unwrap(),
+            Method::new_from_name("TestAddrFromKey").unwrap(),
+            Method::new_from_name("TestFailDeserializationAddrFromKey").unwrap(),
+            Method::new_from_name("TestFailNoParametersAddrFromKey").unwrap(),
+            Method::new_from_name("TestFailKeyAddrFromKey").unwrap(),
+            Method::new_from_name("TestSign").unwrap(),
+            Method::new_from_name("TestFailDeserializationSign").unwrap(),
+            Method::new_from_name("TestFailNoParametersSign").unwrap(),
+            Method::new_from_name("TestFailLengthSign").unwrap(),
+            Method::new_from_name("TestFailKeySign").unwrap(),
+            Method::new_from_name("TestMockSignatures").unwrap(),
+            Method::new_from_name("TestFailDeserializationMockSignatures").unwrap(),
+            Method::new_from_name("TestFailNoParametersMockSignatures").unwrap(),
+            Method::new_from_name("TestDeploy").unwrap(),
+            Method::new_from_name("TestDeployFailingConstructor").unwrap(),
+            Method::new_from_name("TestFailUnknownDeploy").unwrap(),
+            Method::new_from_name("TestFailDeserializationDeploy").unwrap(),
+            Method::new_from_name("TestFailNoParametersDeploy").unwrap(),
+            Method::new_from_name("TestGasLimit").unwrap(),
+            Method::new_from_name("TestFailExceedGasLimit").unwrap(),
+            Method::new_from_name("TestFailLoopGasLimit").unwrap(),
+            Method::new_from_name("TestFailMockSignaturesGasLimit").unwrap(),
+            Method::new_from_name("TestFailDeserializationGasLimit").unwrap(),
+            Method::new_from_name("TestFailNoParametersGasLimit").unwrap(),
+            Method::new_from_name("TestLoad").unwrap(),
+            Method::new_from_name("TestFailDeserializationLoad").unwrap(),
+            Method::new_from_name("TestFailNoParametersLoad").unwrap(),
+            Method::new_from_name("TestFailUnknownLoadBlock").unwrap(),
+            Method::new_from_name("TestFailDeserializationLoadBlock").unwrap(),
+            Method::new_from_name("TestFailNoParametersLoadBlock").unwrap(),
+            Method::new_from_name("TestFailUnreachableAlter").unwrap(),
+            Method::new_from_name("TestStore").unwrap(),
+            Method::new_from_name("TestFailCodecStore").unwrap(),
+            Method::new_from_name("TestFailDeserializationStore").unwrap(),
+            Method::new_from_name("TestFailNoParametersStore").unwrap(),
+            Method::new_from_name("TestAlterWith").unwrap(),
+            Method::new_from_name("TestAlterWithOtherShape").unwrap(),
+            Method::new_from_name("TestFailUnreachableAlterWith").unwrap(),
+            Method::new_from_name("TestFailDeserializationAlterWith").unwrap(),
+            Method::new_from_name("TestFailNoParametersAlterWith").unwrap(),
+            Method::new_from_name("TestFixture").unwrap(),
+            Method::new_from_name("TestFailUnknownFixture").unwrap(),
         ],
     };
     let test_actor = WasmActor::new(String::from("Target.t.wasm"), test_wasm_bin, test_abi);
@@ -397,17 +713,135 @@ fn test_cheatcodes() {
                         "TestFailDeserializationPrank" => "Could not deserialize parameters for Prank cheatcode",
                         "TestFailNoParametersPrank" => "No parameters provided for Prank cheatcode",
                         "TestFailAddressTypePrank" => "Address parameter for Prank should have a valid ActorID",
+                        "TestFailDeserializationStartPrank" => "Could not deserialize parameters for StartPrank cheatcode",
+                        "TestFailNoParametersStartPrank" => "No parameters provided for StartPrank cheatcode",
+                        "TestFailAddressTypeStartPrank" => "Address parameter for StartPrank should have a valid ActorID",
                         "TestFailDeserializationTrick" => "Could not deserialize parameters for Trick cheatcode",
                         "TestFailNoParametersTrick" => "No parameters provided for Trick cheatcode",
                         "TestFailAddressTypeTrick" => "Address parameter for Trick should have a valid ActorID",
                         "TestFailDeserializationAlter" => "Could not deserialize parameters for Alter cheatcode",
                         "TestFailNoParametersAlter" => "No parameters provided for Alter cheatcode",
                         "TestFailInvalidAddressAlter" => "No actor ID associated with target for Alter cheatcode",
-                        "TestFailInvalidCidAlter" => "Invalid Cid passed to Alter cheatcode"
+                        "TestFailInvalidCidAlter" => "Invalid Cid passed to Alter cheatcode",
+                        "TestFailDeserializationExpectRevert" => "Could not deserialize parameters for ExpectRevert cheatcode",
+                        "TestFailNoParametersExpectRevert" => "No parameters provided for ExpectRevert cheatcode",
+                        "TestFailSuccessExitCodeExpectRevert" => "Exit code parameter for ExpectRevert should not be a success",
+                        "TestFailDeserializationDeal" => "Could not deserialize parameters for Deal cheatcode",
+                        "TestFailNoParametersDeal" => "No parameters provided for Deal cheatcode",
+                        "TestFailNegativeBalanceDeal" => "Balance parameter for Deal should not be negative",
+                        "TestFailInvalidAddressDeal" => "No actor ID associated with target for Deal cheatcode",
+                        "TestFailDeserializationExpectEmit" => "Could not deserialize parameters for ExpectEmit cheatcode",
+                        "TestFailNoParametersExpectEmit" => "No parameters provided for ExpectEmit cheatcode",
+                        "TestFailEmptyEventExpectEmit" => "Event parameter for ExpectEmit should have at least one entry",
+                        "TestFailDeserializationRevertTo" => "Could not deserialize parameters for RevertTo cheatcode",
+                        "TestFailNoParametersRevertTo" => "No parameters provided for RevertTo cheatcode",
+                        "TestFailInvalidIdRevertTo" => "No snapshot with given ID for RevertTo cheatcode",
+                        "TestFailDeserializationMockCall" => "Could not deserialize parameters for MockCall cheatcode",
+                        "TestFailNoParametersMockCall" => "No parameters provided for MockCall cheatcode",
+                        "TestFailDeserializationExpectCall" => "Could not deserialize parameters for ExpectCall cheatcode",
+                        "TestFailNoParametersExpectCall" => "No parameters provided for ExpectCall cheatcode",
+                        "TestFailDeserializationSetChainRandomness" => "Could not deserialize parameters for SetChainRandomness cheatcode",
+                        "TestFailNoParametersSetChainRandomness" => "No parameters provided for SetChainRandomness cheatcode",
+                        "TestFailLengthSetChainRandomness" => "Randomness parameter for SetChainRandomness should be 32 bytes long",
+                        "TestFailDeserializationSetBeaconRandomness" => "Could not deserialize parameters for SetBeaconRandomness cheatcode",
+                        "TestFailNoParametersSetBeaconRandomness" => "No parameters provided for SetBeaconRandomness cheatcode",
+                        "TestFailLengthSetBeaconRandomness" => "Randomness parameter for SetBeaconRandomness should be 32 bytes long",
+                        "TestFailDeserializationSetTipsetCid" => "Could not deserialize parameters for SetTipsetCid cheatcode",
+                        "TestFailNoParametersSetTipsetCid" => "No parameters provided for SetTipsetCid cheatcode",
+                        "TestFailInvalidCidSetTipsetCid" => "Invalid Cid passed to SetTipsetCid cheatcode",
+                        "TestFailDeserializationMockConsensusFault" => "Could not deserialize parameters for MockConsensusFault cheatcode",
+                        "TestFailNoParametersMockConsensusFault" => "No parameters provided for MockConsensusFault cheatcode",
+                        "TestFailFaultTypeMockConsensusFault" => "Invalid fault type passed to MockConsensusFault cheatcode",
+                        "TestFailAddressTypeMockConsensusFault" => "Target address for MockConsensusFault cheatcode should be an ID address",
+                        "TestFailDeserializationMockProofs" => "Could not deserialize parameters for MockProofs cheatcode",
+                        "TestFailNoParametersMockProofs" => "No parameters provided for MockProofs cheatcode",
+                        "TestFailDeserializationExpectVerifyProof" => "Could not deserialize parameters for ExpectVerifyProof cheatcode",
+                        "TestFailNoParametersExpectVerifyProof" => "No parameters provided for ExpectVerifyProof cheatcode",
+                        "TestFailKindExpectVerifyProof" => "Invalid proof kind passed to ExpectVerifyProof cheatcode",
+                        "TestFailDeserializationNewAccount" => "Could not deserialize parameters for NewAccount cheatcode",
+                        "TestFailNoParametersNewAccount" => "No parameters provided for NewAccount cheatcode",
+                        "TestFailKeyTypeNewAccount" => "Invalid key type passed to NewAccount cheatcode",
+                        "TestFailDeserializationAddrFromKey" => "Could not deserialize parameters for AddrFromKey cheatcode",
+                        "TestFailNoParametersAddrFromKey" => "No parameters provided for AddrFromKey cheatcode",
+                        "TestFailKeyAddrFromKey" => "Invalid private key passed to AddrFromKey cheatcode",
+                        "TestFailDeserializationSign" => "Could not deserialize parameters for Sign cheatcode",
+                        "TestFailNoParametersSign" => "No parameters provided for Sign cheatcode",
+                        "TestFailLengthSign" => "Digest parameter for Sign cheatcode should be 32 bytes long",
+                        "TestFailKeySign" => "is not an account",
+                        "TestFailDeserializationMockSignatures" => "Could not deserialize parameters for MockSignatures cheatcode",
+                        "TestFailNoParametersMockSignatures" => "No parameters provided for MockSignatures cheatcode",
+                        "TestFailUnknownDeploy" => "Unknown actor passed to Deploy cheatcode: Unknown.wasm",
+                        "TestFailDeserializationDeploy" => "Could not deserialize parameters for Deploy cheatcode",
+                        "TestFailNoParametersDeploy" => "No parameters provided for Deploy cheatcode",
+                        "TestFailDeserializationGasLimit" => "Could not deserialize parameters for GasLimit cheatcode",
+                        "TestFailNoParametersGasLimit" => "No parameters provided for GasLimit cheatcode",
+                        "TestFailDeserializationLoad" => "Could not deserialize parameters for Load cheatcode",
+                        "TestFailNoParametersLoad" => "No parameters provided for Load cheatcode",
+                        "TestFailUnknownLoadBlock" => "No block found for Cid",
+                        "TestFailDeserializationLoadBlock" => "Could not deserialize parameters for LoadBlock cheatcode",
+                        "TestFailNoParametersLoadBlock" => "No parameters provided for LoadBlock cheatcode",
+                        "TestFailUnreachableAlter" => "of the new state is not in the blockstore for Alter cheatcode",
+                        "TestFailCodecStore" => "Unsupported codec 112 passed to Store cheatcode",
+                        "TestFailDeserializationStore" => "Could not deserialize parameters for Store cheatcode",
+                        "TestFailNoParametersStore" => "No parameters provided for Store cheatcode",
+                        "TestFailUnreachableAlterWith" => "of the new state is not in the blockstore for AlterWith cheatcode",
+                        "TestFailDeserializationAlterWith" => "Could not deserialize parameters for AlterWith cheatcode",
+                        "TestFailNoParametersAlterWith" => "No parameters provided for AlterWith cheatcode",
+                        "TestFailUnknownFixture" => "No fixture actor named Unknown for Fixture cheatcode"
+                    );
+                }
+                // Tests using more gas than their limit run out of gas whatever their type.
+                (_, TestResultType::OutOfGas(apply_ret)) => {
+                    assert_eq!(apply_ret.msg_receipt.exit_code, ExitCode::SYS_OUT_OF_GAS);
+                    match result.method().name() {
+                        "TestFailExceedGasLimit" => assert!(apply_ret.msg_receipt.gas_used > 1),
+                        // The limit stops the test actor long before the message gas limit.
+                        "TestFailLoopGasLimit" => {
+                            assert!(apply_ret.msg_receipt.gas_used < 20_000_000)
+                        }
+                        // Mocked syscalls are charged against the limit too.
+                        "TestFailMockSignaturesGasLimit" => {
+                            assert!(apply_ret.msg_receipt.gas_used < 100_000_000)
+                        }
+                        name => panic!("test {name} should not run out of gas"),
+                    }
+                }
+                // Expectations not met fail the test whatever its type.
+                (MethodType::TestFail, TestResultType::ExpectationFailed(failure)) => {
+                    let expected = match result.method().name() {
+                        "TestFailCallSucceededExpectRevert" => "did not revert, expected exit code",
+                        "TestFailExitCodeExpectRevert" => {
+                            "reverted with exit code 22, expected exit code 16"
+                        }
+                        "TestFailMessageExpectRevert" => {
+                            "reverted with message \"Unknown method number\", expected \"Another message\""
+                        }
+                        "TestFailEventExpectEmit" => {
+                            "did not emit expected event [hello: 0x6b797468657261 (flags 3)]"
+                        }
+                        "TestFailNoEventExpectEmit" => {
+                            "did not emit expected event [hello: 0x776f726c64 (flags 3)]"
+                        }
+                        "TestFailCountExpectCall" => "Expected 2 call(s) to",
+                        "TestFailValueExpectCall" => "but 0 were sent",
+                        "TestFailAbortExpectCall" => "Expected 1 call(s) to",
+                        "TestFailUnverifiedExpectVerifyProof" => {
+                            "Expected 1 proof verification(s) but ReplicaUpdate proof was not verified"
+                        }
+                        name => panic!("test {name} should not fail an expectation: {failure}"),
+                    };
+                    assert!(
+                        failure.contains(expected),
+                        "unexpected failure for {}: {failure}",
+                        result.method().name()
                     );
                 }
                 (MethodType::Test, TestResultType::Passed(apply_ret)) => {
                     assert_eq!(apply_ret.msg_receipt.exit_code, ExitCode::OK);
+                    if result.method().name() == "TestExpectEmit" {
+                        assert_eq!(result.events().len(), 1);
+                        assert_eq!(result.events()[0].event.entries[0].key, "hello");
+                    }
                 }
                 apply_ret => {
                     panic!("test against cheatcodes test actor should be valid: {apply_ret:?}")
@@ -415,3 +849,274 @@ fn test_cheatcodes() {
             }),
     }
 }
+
+#[test]
+fn test_state_after_test() {
+    // Instantiate tester
+    let mut tester = Tester::new();
+
+    // Set target actor
+    set_target_actor(
+        &mut tester,
+        String::from("Target.wasm"),
+        Vec::from(BASIC_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: Some(Method::new_from_name("Constructor").unwrap()),
+            set_up: None,
+            methods: vec![Method::new_from_name("HelloWorld").unwrap()],
+        },
+    );
+    let target = *tester
+        .deployed_actor()
+        .expect("Target Actor should be deployed")
+        .address();
+
+    // Set test actor
+    let test_actor = WasmActor::new(
+        String::from("Cheatcodes.t.wasm"),
+        Vec::from(CHEATCODES_TEST_ACTOR_BINARY),
+        Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![Method::new_from_name("TestAlterWith").unwrap()],
+        },
+    );
+
+    let test_res = tester
+        .test(&test_actor, None)
+        .expect("Could not run test when testing the state after a test");
+    assert_eq!(test_res.len(), 1);
+    assert!(test_res[0].passed());
+
+    // The state altered by the test is kept with its result, the state of the tester is not.
+    let (who_am_i,): (String,) = tester
+        .decode_state_after(&test_res[0], &target)
+        .expect("Should be able to decode the target state after the test");
+    assert_eq!(who_am_i, "I am new value");
+    let (who_am_i,): (String,) = tester
+        .decode_state(&target)
+        .expect("Should be able to decode the target state");
+    assert_eq!(who_am_i, "Basic Target Actor");
+}
+
+#[test]
+fn test_circ_supply_across_messages() {
+    // Instantiate tester
+    let mut tester = Tester::new();
+
+    // Set target actor
+    set_target_actor(
+        &mut tester,
+        String::from("Target.wasm"),
+        Vec::from(BASIC_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![],
+        },
+    );
+    let target = *tester
+        .deployed_actor()
+        .expect("Target Actor should be deployed")
+        .address();
+
+    // Deal tokens to the target actor in a message of its own
+    let from = tester.account().1;
+    let call_res = tester
+        .call_cbor(
+            &from,
+            &Address::new_id(98),
+            "Deal",
+            &(target, TokenAmount::from_atto(1000)),
+            TokenAmount::zero(),
+            None,
+        )
+        .expect("Could not call Deal cheatcode");
+    assert_eq!(call_res.ret.msg_receipt.exit_code, ExitCode::OK);
+
+    // The dealt tokens are still part of the circulating supply of the next messages
+    let test_actor = WasmActor::new(
+        String::from("Cheatcodes.t.wasm"),
+        Vec::from(CHEATCODES_TEST_ACTOR_BINARY),
+        Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![Method::new_from_name("TestDealKept").unwrap()],
+        },
+    );
+
+    let test_res = tester
+        .test(&test_actor, None)
+        .expect("Could not run test when testing the circulating supply");
+    assert_eq!(test_res.len(), 1);
+    assert!(test_res[0].passed());
+}
+
+#[test]
+fn test_fuzz() {
+    // Instantiate tester
+    let mut tester = Tester::new();
+    tester.set_fuzz_config(FuzzConfig { runs: 32, seed: 1 });
+
+    // Set target actor
+    set_target_actor(
+        &mut tester,
+        String::from("Target.wasm"),
+        Vec::from(BASIC_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: None,
+            set_up: None,
+            methods: vec![],
+        },
+    );
+
+    // Set test actor
+    let test_wasm_bin: Vec<u8> = Vec::from(FUZZ_TEST_ACTOR_BINARY);
+    let test_abi = Abi {
+        constructor: None,
+        set_up: None,
+        methods: vec![
+            Method::new_from_name("TestFuzzCommutative")
+                .unwrap()
+                .with_params(vec![ParamType::U64, ParamType::U64]),
+            Method::new_from_name("TestFuzzBelowThreshold")
+                .unwrap()
+                .with_params(vec![ParamType::U64]),
+            Method::new_from_name("TestFuzzShortBytes")
+                .unwrap()
+                .with_params(vec![ParamType::Bytes]),
+            Method::new_from_name("TestFuzzOutOfGas")
+                .unwrap()
+                .with_params(vec![ParamType::U64]),
+        ],
+    };
+    let test_actor = WasmActor::new(String::from("Target.t.wasm"), test_wasm_bin, test_abi);
+
+    match tester.test(&test_actor, None) {
+        Err(_) => {
+            panic!("Could not run test when testing Tester for fuzz tests")
+        }
+        Ok(test_res) => {
+            assert_eq!(test_res.len(), 4);
+            test_res.iter().for_each(|result| {
+                let report = result
+                    .fuzz_report()
+                    .expect("TestFuzz methods should have a fuzz report");
+                match result.method().name() {
+                    "TestFuzzCommutative" => {
+                        assert!(result.passed());
+                        assert_eq!(report.runs(), 32);
+                        assert!(report.counterexample().is_none());
+                    }
+                    "TestFuzzBelowThreshold" => {
+                        match result.ret() {
+                            TestResultType::Failed(apply_ret) => assert_eq!(
+                                apply_ret.msg_receipt.exit_code,
+                                ExitCode::USR_ASSERTION_FAILED
+                            ),
+                            _ => panic!("TestFuzzBelowThreshold should be failing"),
+                        }
+                        assert_eq!(report.counterexample(), Some(&[FuzzValue::U64(1000)][..]));
+                    }
+                    "TestFuzzShortBytes" => {
+                        assert!(!result.passed());
+                        assert_eq!(
+                            report.counterexample(),
+                            Some(&[FuzzValue::Bytes(vec![0; 4])][..])
+                        );
+                    }
+                    "TestFuzzOutOfGas" => {
+                        // The default gas limit is not set on purpose, running out of it is a
+                        // regular failure.
+                        match result.ret() {
+                            TestResultType::Failed(apply_ret) => assert_eq!(
+                                apply_ret.msg_receipt.exit_code,
+                                ExitCode::SYS_OUT_OF_GAS
+                            ),
+                            _ => panic!("TestFuzzOutOfGas should be failing"),
+                        }
+                        assert_eq!(report.runs(), 1);
+                        assert_eq!(report.counterexample(), Some(&[FuzzValue::U64(0)][..]));
+                    }
+                    name => panic!("Test case not handled for: {}", name),
+                }
+            })
+        }
+    }
+}
+
+#[test]
+fn test_invariant() {
+    // Instantiate tester
+    let mut tester = Tester::new();
+    tester.set_invariant_config(InvariantConfig { runs: 8, depth: 10 });
+
+    // Set target actor
+    set_target_actor(
+        &mut tester,
+        String::from("Target.wasm"),
+        Vec::from(INVARIANT_TARGET_ACTOR_BINARY),
+        Abi {
+            constructor: Some(Method::new_from_name("Constructor").unwrap()),
+            set_up: None,
+            methods: vec![
+                Method::new_from_name("Increment").unwrap(),
+                Method::new_from_name("Add")
+                    .unwrap()
+                    .with_params(vec![ParamType::U64]),
+                Method::new_from_name("Reset").unwrap(),
+                Method::new_from_name("Count").unwrap(),
+            ],
+        },
+    );
+
+    // Set test actor
+    let test_wasm_bin: Vec<u8> = Vec::from(INVARIANT_TEST_ACTOR_BINARY);
+    let test_abi = Abi {
+        constructor: None,
+        set_up: None,
+        methods: vec![
+            Method::new_from_name("InvariantCountBelowThreshold").unwrap(),
+            Method::new_from_name("InvariantCountReadable").unwrap(),
+        ],
+    };
+    let test_actor = WasmActor::new(String::from("Target.t.wasm"), test_wasm_bin, test_abi);
+
+    match tester.test(&test_actor, None) {
+        Err(_) => {
+            panic!("Could not run test when testing Tester for invariants")
+        }
+        Ok(test_res) => {
+            assert_eq!(test_res.len(), 2);
+            test_res.iter().for_each(|result| {
+                let report = result
+                    .invariant_report()
+                    .expect("Invariant methods should have an invariant report");
+                match result.method().name() {
+                    "InvariantCountBelowThreshold" => {
+                        match result.ret() {
+                            TestResultType::Failed(apply_ret) => assert_eq!(
+                                apply_ret.msg_receipt.exit_code,
+                                ExitCode::USR_ASSERTION_FAILED
+                            ),
+                            _ => panic!("InvariantCountBelowThreshold should be failing"),
+                        }
+                        let sequence = report
+                            .counterexample()
+                            .expect("Failing invariant should have a call sequence");
+                        assert_eq!(sequence.len(), 1);
+                        assert_eq!(sequence[0].method().name(), "Add");
+                        assert_eq!(sequence[0].inputs(), &[FuzzValue::U64(1000)]);
+                    }
+                    "InvariantCountReadable" => {
+                        assert!(result.passed());
+                        assert_eq!(report.runs(), 8);
+                        assert_eq!(report.calls(), 80);
+                        assert!(report.counterexample().is_none());
+                    }
+                    name => panic!("Test case not handled for: {}", name),
+                }
+            })
+        }
+    }
+}